@@ -1,4 +1,4 @@
-use openocean_sdk::{Chain, OpenoceanClient, OpenoceanConfig, OpenoceanError, Swap};
+use openocean_sdk::{Chain, OpenoceanClient, OpenoceanConfig, OpenoceanError};
 use std::time::Duration;
 
 #[tokio::main]
@@ -15,12 +15,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Example 1: Successful request
     println!("1. Successful request:");
-    let swap = Swap::new(&client);
+    let swap = client.swap();
     match swap.get_price(Chain::Bsc).await {
         Ok(gas_response) => {
             println!("✅ Success! Gas prices retrieved:");
-            println!("  Standard: {} Gwei", gas_response.data.standard);
-            println!("  Fast: {} Gwei", gas_response.data.fast);
+            println!("  Standard: {} Gwei", gas_response.standard);
+            println!("  Fast: {} Gwei", gas_response.fast);
         }
         Err(e) => {
             println!("❌ Error: {}", e);
@@ -36,7 +36,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Try to get token list
     match swap.get_token_list(Chain::Bsc).await {
         Ok(token_list) => {
-            let token_list = token_list.data.unwrap();
             println!("✅ Token list retrieved: {} tokens", token_list.len());
         }
         Err(e) => {
@@ -78,6 +77,26 @@ fn print_error_details(error: &OpenoceanError) {
             println!("  🔍 Parse Error: {}", body);
             println!("  💡 This could be due to unexpected response format or JSON parsing issues.");
         }
+        OpenoceanError::Api { code, kind, message, endpoint } => {
+            println!("  🧾 API Error: code {} ({:?}) from {}: {}", code, kind, endpoint, message);
+            println!("  💡 The request reached OpenOcean but was rejected by the API.");
+        }
+        OpenoceanError::RateLimited { retry_after } => {
+            println!("  ⏳ Rate Limited: retry after {:?}", retry_after);
+            println!("  💡 The client-side rate limiter rejected the request.");
+        }
+        OpenoceanError::InvalidParams(msg) => {
+            println!("  📝 Invalid Params: {}", msg);
+            println!("  💡 Check the fields passed to the params builder.");
+        }
+        OpenoceanError::UnsupportedChain { chain, feature } => {
+            println!("  ⛓️ Unsupported Chain: {} is not available on {}", feature, chain);
+            println!("  💡 Use another chain, or override the table with OpenoceanConfigBuilder::capability.");
+        }
+        OpenoceanError::ReceiptTimeout { hash, waited } => {
+            println!("  ⌛ Receipt Timeout: {} not confirmed after {:?}", hash, waited);
+            println!("  💡 The transaction may still be pending; keep polling or check the explorer.");
+        }
         OpenoceanError::Internal(msg) => {
             println!("  ⚙️ Internal Error: {}", msg);
             println!("  💡 This is an internal SDK error. Please report this issue.");
@@ -93,7 +112,7 @@ async fn demonstrate_error_types() {
         .build();
     
     if let Ok(client) = OpenoceanClient::new(invalid_config) {
-        let swap = Swap::new(&client);
+        let swap = client.swap();
         match swap.get_price(Chain::Bsc).await {
             Err(OpenoceanError::Network(msg)) => {
                 println!("✅ Network error caught: {}", msg);
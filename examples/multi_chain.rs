@@ -10,7 +10,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build();
     
     let client = OpenoceanClient::new(config)?;
-    let swap = Swap::new(&client);
+    let swap = client.swap();
     
     println!("=== OpenOcean SDK Multi-Chain Example ===\n");
     
@@ -70,15 +70,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Note: This example will test all {} EVM-compatible chains", evm_chains.len());
     println!("For demonstration purposes, we'll limit to first 10 chains to avoid overwhelming output.\n");
     
-    for (chain, name) in evm_chains.iter().take(10) {
+    // Handles are cheap to clone and 'static, so each chain gets its own task
+    let tasks: Vec<_> = evm_chains
+        .iter()
+        .take(10)
+        .map(|(chain, name)| {
+            let swap = client.swap();
+            let (chain, name) = (chain.clone(), *name);
+            tokio::spawn(async move { (name, swap.get_price(chain).await) })
+        })
+        .collect();
+    
+    for task in tasks {
+        let (name, result) = task.await?;
         println!("--- {} ---", name);
         
-        match swap.get_price(*chain).await {
+        match result {
             Ok(gas_response) => {
                 println!("✅ Gas prices:");
-                println!("  Standard: {:.2} Gwei", gas_response.data.standard);
-                println!("  Fast: {:.2} Gwei", gas_response.data.fast);
-                println!("  Instant: {:.2} Gwei", gas_response.data.instant);
+                println!("  Standard: {:.2} Gwei", gas_response.standard);
+                println!("  Fast: {:.2} Gwei", gas_response.fast);
+                println!("  Instant: {:.2} Gwei", gas_response.instant);
             }
             Err(e) => {
                 println!("❌ Failed to get gas prices: {}", e);
@@ -94,9 +106,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     for (chain, name) in evm_chains.iter().take(5) {
         println!("--- {} Token Count ---", name);
         
-        match swap.get_token_list(*chain).await {
+        match swap.get_token_list(chain.clone()).await {
             Ok(token_list) => {
-                let token_list = token_list.data.unwrap();
                 println!("✅ Total tokens: {}", token_list.len());
                 
                 // Show some popular tokens if available
@@ -0,0 +1,56 @@
+use openocean_sdk::{models::swap::QuoteParams, Chain, OpenoceanClient, OpenoceanConfig};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+// Exports SDK spans to an OTLP collector (Jaeger, Tempo, ...) and prints them
+// to stdout. Start a collector first, e.g.
+//   docker run -p 4317:4317 -p 16686:16686 jaegertracing/all-in-one
+// then run with `OTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4317`.
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .unwrap_or_else(|_| "http://localhost:4317".to_string());
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name("openocean-example").build())
+        .build();
+
+    // Request spans are emitted at DEBUG level under the `openocean_sdk` target
+    tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info,openocean_sdk=debug")))
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("openocean-example")))
+        .init();
+
+    let client = OpenoceanClient::new(OpenoceanConfig::default())?;
+    let swap = client.swap();
+
+    let params = QuoteParams::builder()
+        .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+        .out_token_address("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+        .amount_decimals("5000000000000000000")
+        .gas_price_decimals("1000000000")
+        .slippage("1")
+        .build()?;
+
+    match swap.quote(Chain::Bsc, &params).await {
+        Ok(quote) => tracing::info!(out_amount = %quote.out_amount, "quote received"),
+        Err(e) => tracing::error!(error = %e, "quote failed"),
+    }
+
+    if let Err(e) = swap.get_gas_price(Chain::Bsc).await {
+        tracing::error!(error = %e, "gas price failed");
+    }
+
+    // Flush pending spans before exiting
+    provider.shutdown()?;
+    Ok(())
+}
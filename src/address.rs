@@ -0,0 +1,386 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{Chain, ChainFamily, OpenoceanError};
+
+
+
+/// A validated on-chain address.
+///
+/// The format is detected on parse, or checked against a chain family with
+/// [`Address::parse_for`], and the address is stored normalized:
+///
+/// - EVM: `0x` + 40 hex digits, EIP-55 checksummed (mixed-case input must
+///   carry a valid checksum)
+/// - Solana: base58, 32 bytes
+/// - Near: named (`wrap.near`) or implicit (64 lowercase hex) account ids
+/// - Starknet: `0x` + up to 64 hex digits, padded to 64 and lowercased
+/// - Ontology: base58, 25 bytes
+///
+/// Addresses in responses are read leniently with [`Address::from_response`],
+/// so one unexpected address can't fail a whole token list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Address {
+    family: Option<ChainFamily>,
+    value: String,
+}
+
+impl Address {
+    /// Parses an address of any supported family.
+    ///
+    /// A 40-digit hex address is taken as EVM; use [`Address::parse_for`]
+    /// with [`ChainFamily::Starknet`] when it is a Starknet felt.
+    pub fn parse(s: &str) -> Result<Self, OpenoceanError> {
+        let s = s.trim();
+        let family = if has_hex_prefix(s) {
+            match s.len() {
+                42 => ChainFamily::Evm,
+                _ => ChainFamily::Starknet,
+            }
+        } else {
+            match bs58::decode(s).into_vec().map(|b| b.len()) {
+                Ok(32) => ChainFamily::Solana,
+                Ok(25) => ChainFamily::Ontology,
+                _ => ChainFamily::Near,
+            }
+        };
+        Self::parse_for(family, s)
+    }
+
+    /// Parses an address in the format of `family`.
+    pub fn parse_for(family: ChainFamily, s: &str) -> Result<Self, OpenoceanError> {
+        let s = s.trim();
+        normalize(family, s)
+            .map(|value| Self { family: Some(family), value })
+            .map_err(|reason| invalid(s, &reason))
+    }
+
+    /// Parses an address for `chain`. Chains without registry metadata accept
+    /// any supported format.
+    pub fn for_chain(chain: &Chain, s: &str) -> Result<Self, OpenoceanError> {
+        match chain.family() {
+            Some(family) => Self::parse_for(family, s),
+            None => Self::parse(s),
+        }
+    }
+
+    /// Reads an address reported by the API without rejecting it.
+    ///
+    /// Mixed-case EVM addresses with a bad checksum are re-checksummed.
+    /// Anything else that doesn't parse (e.g. Tron or Sui addresses) is kept
+    /// verbatim with no family.
+    pub fn from_response(s: &str) -> Self {
+        let s = s.trim();
+        if let Ok(address) = Self::parse(s) {
+            return address;
+        }
+        match normalize_evm(&s.to_ascii_lowercase()) {
+            Ok(value) => Self { family: Some(ChainFamily::Evm), value },
+            Err(_) => Self { family: None, value: s.to_string() },
+        }
+    }
+
+    /// The EVM zero address.
+    pub fn zero() -> Self {
+        Self { family: Some(ChainFamily::Evm), value: format!("0x{}", "0".repeat(40)) }
+    }
+
+    /// The EVM address with the given 20 bytes.
+    pub fn from_evm_bytes(bytes: [u8; 20]) -> Self {
+        let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        Self { family: Some(ChainFamily::Evm), value: to_checksum(&hex) }
+    }
+
+    /// The detected family, `None` for response addresses in a format the
+    /// SDK doesn't know.
+    pub fn family(&self) -> Option<ChainFamily> {
+        self.family
+    }
+
+    /// The normalized address.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// The 20 address bytes, for EVM addresses.
+    pub fn evm_bytes(&self) -> Option<[u8; 20]> {
+        if self.family != Some(ChainFamily::Evm) {
+            return None;
+        }
+        let mut out = [0u8; 20];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&self.value[2 + 2 * i..4 + 2 * i], 16).ok()?;
+        }
+        Some(out)
+    }
+
+    /// Whether the address can be used on `chain`. 20-byte hex addresses are
+    /// also valid Starknet felts.
+    pub fn is_valid_for(&self, chain: &Chain) -> bool {
+        match chain.family() {
+            None => true,
+            Some(ChainFamily::Starknet) => matches!(self.family, Some(ChainFamily::Starknet | ChainFamily::Evm)),
+            Some(family) => self.family == Some(family),
+        }
+    }
+}
+
+/// Rejects addresses that can't be used on `chain` before the request is
+/// sent.
+pub(crate) fn ensure_chain<'a>(
+    chain: &Chain,
+    addresses: impl IntoIterator<Item = &'a Address>,
+) -> Result<(), OpenoceanError> {
+    match addresses.into_iter().find(|a| !a.is_valid_for(chain)) {
+        Some(a) => Err(OpenoceanError::InvalidParams(format!("{a} is not a valid {chain} address"))),
+        None => Ok(()),
+    }
+}
+
+fn invalid(s: &str, reason: &str) -> OpenoceanError {
+    OpenoceanError::InvalidParams(format!("invalid address {s:?}: {reason}"))
+}
+
+fn has_hex_prefix(s: &str) -> bool {
+    s.starts_with("0x") || s.starts_with("0X")
+}
+
+fn normalize(family: ChainFamily, s: &str) -> Result<String, String> {
+    match family {
+        ChainFamily::Evm => normalize_evm(s),
+        ChainFamily::Starknet => normalize_starknet(s),
+        ChainFamily::Solana => decode_base58(s, 32).map(|_| s.to_string()),
+        ChainFamily::Ontology => match decode_base58(s, 25)?.first() {
+            Some(0x17) => Ok(s.to_string()),
+            _ => Err("not an Ontology address".into()),
+        },
+        ChainFamily::Near => normalize_near(s),
+    }
+}
+
+fn normalize_evm(s: &str) -> Result<String, String> {
+    let hex = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .ok_or("missing 0x prefix")?;
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("expected 40 hex digits".into());
+    }
+
+    let checksummed = to_checksum(hex);
+    let mixed_case = hex.bytes().any(|b| b.is_ascii_lowercase()) && hex.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed_case && checksummed[2..] != *hex {
+        return Err("invalid EIP-55 checksum".into());
+    }
+    Ok(checksummed)
+}
+
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(data);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+/// EIP-55: uppercase each letter whose nibble in keccak256(lowercase hex) is >= 8.
+fn to_checksum(hex: &str) -> String {
+    let lower = hex.to_ascii_lowercase();
+    let hash = keccak256(lower.as_bytes());
+
+    let mut out = String::with_capacity(42);
+    out.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        out.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+    }
+    out
+}
+
+fn normalize_starknet(s: &str) -> Result<String, String> {
+    let hex = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .ok_or("missing 0x prefix")?;
+    if hex.is_empty() || hex.len() > 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("expected up to 64 hex digits".into());
+    }
+
+    let padded = format!("{:0>64}", hex.to_ascii_lowercase());
+    // felts are below 2^251 + 17 * 2^192 + 1
+    if padded.as_bytes()[0] != b'0' || padded.as_bytes()[1] > b'8' {
+        return Err("out of the Starknet field range".into());
+    }
+    Ok(format!("0x{padded}"))
+}
+
+fn decode_base58(s: &str, len: usize) -> Result<Vec<u8>, String> {
+    let bytes = bs58::decode(s).into_vec().map_err(|e| e.to_string())?;
+    match bytes.len() == len {
+        true => Ok(bytes),
+        false => Err(format!("expected {len} bytes, got {}", bytes.len())),
+    }
+}
+
+/// Named accounts (`alice.near`, `token.v2.ref-finance.near`) and implicit
+/// accounts (64 lowercase hex digits).
+fn normalize_near(s: &str) -> Result<String, String> {
+    if !(2..=64).contains(&s.len()) {
+        return Err("expected 2 to 64 characters".into());
+    }
+
+    let part_ok = |part: &str| {
+        !part.is_empty()
+            && part.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+            && !part.starts_with(['-', '_'])
+            && !part.ends_with(['-', '_'])
+            && !part.contains("--")
+            && !part.contains("__")
+            && !part.contains("-_")
+            && !part.contains("_-")
+    };
+    match s.split('.').all(part_ok) {
+        true => Ok(s.to_string()),
+        false => Err("not a Near account id".into()),
+    }
+}
+
+impl FromStr for Address {
+    type Err = OpenoceanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Address {
+    type Error = OpenoceanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Address::parse(s)
+    }
+}
+
+impl TryFrom<String> for Address {
+    type Error = OpenoceanError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Address::parse(&s)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&self.value)
+    }
+}
+
+/// Lenient, see [`Address::from_response`]. User input goes through
+/// [`FromStr`] and the params builders, which validate strictly.
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let s = std::borrow::Cow::<str>::deserialize(de)?;
+        Ok(Address::from_response(&s))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_info::{EVM_NATIVE_ADDRESS, OPENOCEAN_EXCHANGE_V2};
+
+    #[test]
+    fn test_evm_checksum() {
+        // EIP-55 test vectors
+        for addr in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let parsed = Address::parse(&addr.to_ascii_lowercase()).unwrap();
+            assert_eq!((parsed.family(), parsed.as_str()), (Some(ChainFamily::Evm), addr));
+            assert_eq!(Address::parse(addr).unwrap(), parsed);
+        }
+
+        let err = Address::parse("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{err}");
+        // too short for EVM, so it can only be a Starknet felt
+        let short = Address::parse("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").unwrap();
+        assert_eq!(short.family(), Some(ChainFamily::Starknet));
+        assert!(Address::parse_for(ChainFamily::Evm, short.as_str()).is_err());
+
+        for addr in [EVM_NATIVE_ADDRESS, OPENOCEAN_EXCHANGE_V2] {
+            assert_eq!(Address::parse(addr).unwrap().as_str(), addr);
+        }
+        assert_eq!(Address::zero().evm_bytes(), Some([0; 20]));
+    }
+
+    #[test]
+    fn test_non_evm_formats() {
+        let sol = Address::parse("So11111111111111111111111111111111111111112").unwrap();
+        assert_eq!(sol.family(), Some(ChainFamily::Solana));
+
+        let near = Address::parse("token.v2.ref-finance.near").unwrap();
+        assert_eq!(near.family(), Some(ChainFamily::Near));
+        assert!(Address::parse_for(ChainFamily::Near, "Alice.near").is_err());
+
+        let stark = Address::parse("0x49D36570D4e46f48e99674bd3fcc84644DdD6b96F7C741B1562B82f9e004dC7").unwrap();
+        assert_eq!(stark.family(), Some(ChainFamily::Starknet));
+        assert_eq!(stark.as_str(), "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
+
+        let ont = Address::parse("AFmseVrdL9f9oyCzZefL9tG6UbvhUMqNMV").unwrap();
+        assert_eq!(ont.family(), Some(ChainFamily::Ontology));
+
+        assert!(Address::parse("not an address").is_err());
+    }
+
+    #[test]
+    fn test_response_addresses_are_lenient() {
+        let parse = |s: &str| serde_json::from_value::<Address>(serde_json::json!(s)).unwrap();
+
+        // bad checksum: strict parsing fails, responses are re-checksummed
+        let bad = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        assert!(bad.parse::<Address>().is_err());
+        assert_eq!(parse(bad).as_str(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+        // Tron (base58, version 0x41) and Sui (0x + 64 hex, above the felt range)
+        for other in [
+            "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t",
+            "0xdba34672e30cb065b1f93e3ab55318768fd6fef66c15942c9f7cb846e2f900e7",
+        ] {
+            assert!(other.parse::<Address>().is_err());
+            let address = parse(other);
+            assert_eq!((address.family(), address.as_str()), (None, other));
+            assert!(!address.is_valid_for(&Chain::Bsc));
+        }
+    }
+
+    #[test]
+    fn test_chain_check() {
+        let evm = Address::parse("0x55d398326f99059ff775485246999027b3197955").unwrap();
+        let sol = Address::parse("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+
+        assert!(ensure_chain(&Chain::Bsc, [&evm]).is_ok());
+        assert!(ensure_chain(&Chain::Solana, [&sol]).is_ok());
+        assert!(matches!(ensure_chain(&Chain::Bsc, [&evm, &sol]), Err(OpenoceanError::InvalidParams(_))));
+        assert!(ensure_chain(&Chain::Unknown("newchain".into()), [&sol]).is_ok());
+        assert!(Address::for_chain(&Chain::Starknet, "0x55d398326f99059ff775485246999027b3197955").is_ok());
+    }
+}
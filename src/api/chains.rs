@@ -0,0 +1,174 @@
+use crate::{models::base::Envelope, Chain, ChainInfo, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::chains::*;
+
+
+
+
+/// Path of the chain list endpoint. The response is the usual envelope with
+/// one [`SupportedChain`] per chain:
+///
+/// ```json
+/// { "code": 200, "data": [{ "code": "bsc", "chainId": 56, "name": "BNB Chain" }] }
+/// ```
+pub const CHAIN_LIST_PATH: &str = "/v4/chainList";
+
+/// A chain from the static registry merged with what the API reports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainEntry {
+    /// A known variant, or [`Chain::Unknown`] for chains only the API knows.
+    pub chain: Chain,
+    pub code: String,
+    pub chain_id: Option<u64>,
+    pub name: String,
+    /// Static metadata, `None` for chains the SDK has no variant for.
+    pub info: Option<&'static ChainInfo>,
+    /// Whether the API listed this chain on the last refresh.
+    pub listed: bool,
+}
+
+impl ChainEntry {
+    fn from_info(info: &'static ChainInfo) -> Self {
+        Self {
+            chain: info.chain.clone(),
+            code: info.code.to_string(),
+            chain_id: info.chain_id,
+            name: info.name.to_string(),
+            info: Some(info),
+            listed: false,
+        }
+    }
+
+    fn matches(&self, key: &str) -> bool {
+        self.code.eq_ignore_ascii_case(key)
+            || self.name.eq_ignore_ascii_case(key)
+            || key.parse::<u64>().is_ok_and(|id| self.chain_id == Some(id))
+    }
+}
+
+/// The static registry, before any refresh.
+pub(crate) fn builtin_chains() -> Vec<ChainEntry> {
+    Chain::ALL
+        .iter()
+        .filter_map(Chain::info)
+        .map(ChainEntry::from_info)
+        .collect()
+}
+
+/// Merges `remote` into the static registry. Chains matching a known variant
+/// by code or chain id are marked as listed; the rest become `Chain::Unknown`.
+pub(crate) fn merge_chains(remote: &[SupportedChain]) -> Vec<ChainEntry> {
+    let mut entries = builtin_chains();
+
+    for chain in remote {
+        let known = entries.iter_mut().find(|e| {
+            e.code.eq_ignore_ascii_case(&chain.code) || (chain.chain_id.is_some() && e.chain_id == chain.chain_id)
+        });
+
+        match known {
+            Some(entry) => entry.listed = true,
+            None => entries.push(ChainEntry {
+                chain: Chain::Unknown(chain.code.to_ascii_lowercase()),
+                code: chain.code.to_ascii_lowercase(),
+                chain_id: chain.chain_id,
+                name: chain.name.clone().unwrap_or_else(|| chain.code.clone()),
+                info: None,
+                listed: true,
+            }),
+        }
+    }
+
+    entries
+}
+
+
+#[derive(Clone)]
+pub struct Chains {
+    client: OpenoceanClient,
+}
+
+impl OpenoceanClient {
+    pub fn chains(&self) -> Chains {
+        Chains::new(self)
+    }
+}
+
+impl Chains {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
+    }
+
+    /// The raw chain list reported by the API.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.chains.get_chain_list", skip_all))]
+    pub async fn get_chain_list(&self) -> Result<Vec<SupportedChain>, OpenoceanError> {
+        let res: GetChainListResponse = self.client.get_json(CHAIN_LIST_PATH).await?;
+        res.into_result(CHAIN_LIST_PATH)
+    }
+
+    /// Fetches the chain list and merges it into the registry shared by this
+    /// client and its clones.
+    pub async fn refresh(&self) -> Result<Vec<ChainEntry>, OpenoceanError> {
+        let remote = self.get_chain_list().await?;
+        let entries = merge_chains(&remote);
+        *self.client.chain_entries().write().unwrap() = entries.clone();
+        Ok(entries)
+    }
+
+    /// Every chain known so far: the static registry, plus new chains from
+    /// the last [`Chains::refresh`].
+    pub fn known(&self) -> Vec<ChainEntry> {
+        self.client.chain_entries().read().unwrap().clone()
+    }
+
+    /// Finds a chain by code, chain id or name, including chains discovered
+    /// at runtime.
+    pub fn resolve(&self, key: &str) -> Option<Chain> {
+        self.client
+            .chain_entries()
+            .read()
+            .unwrap()
+            .iter()
+            .find(|e| e.matches(key.trim()))
+            .map(|e| e.chain.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expected response shape of [`CHAIN_LIST_PATH`].
+    const CHAIN_LIST: &str = r#"{
+        "code": 200,
+        "data": [
+            { "id": 5, "code": "bsc", "chainId": 56, "name": "BNB Chain" },
+            { "id": 2, "code": "avax", "chainId": "43114", "name": "Avalanche" },
+            { "id": 3, "code": "newchain", "chainId": "999999", "name": "New Chain" },
+            { "id": 4, "code": "movement", "chainId": "SN_MAIN", "name": "Movement" },
+            { "id": 1, "code": "nextchain", "name": "Next Chain" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_merge_chains() {
+        let res: GetChainListResponse = serde_json::from_str(CHAIN_LIST).unwrap();
+        let remote = res.into_result(CHAIN_LIST_PATH).unwrap();
+
+        let entries = merge_chains(&remote);
+        assert_eq!(entries.len(), Chain::ALL.len() + 3);
+
+        let bsc = entries.iter().find(|e| e.chain == Chain::Bsc).unwrap();
+        assert!(bsc.listed && bsc.info.is_some());
+        assert!(!entries.iter().find(|e| e.chain == Chain::Eth).unwrap().listed);
+
+        let new = entries.iter().find(|e| e.code == "newchain").unwrap();
+        assert_eq!(new.chain, Chain::Unknown("newchain".into()));
+        assert_eq!((new.chain_id, new.name.as_str()), (Some(999999), "New Chain"));
+        assert_eq!(entries.iter().find(|e| e.code == "movement").unwrap().chain_id, None);
+
+        // the row id is not a chain id: "nextchain" must not match Ethereum (id 1)
+        let next = entries.iter().find(|e| e.code == "nextchain").unwrap();
+        assert_eq!((next.chain.clone(), next.chain_id), (Chain::Unknown("nextchain".into()), None));
+    }
+}
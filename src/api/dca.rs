@@ -1,4 +1,6 @@
-use crate::{models::dca::{DcaCancelOrderParams, DcaCancelSwapResponse, DcaCreateSwapParams, DcaCreateSwapResponse, GetDcaOrderFillsResponse, GetDcaOrdersResponse}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::base::Envelope, Chain, Feature, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::dca::*;
 
 
 
@@ -6,33 +8,52 @@ use crate::{models::dca::{DcaCancelOrderParams, DcaCancelSwapResponse, DcaCreate
 
 
 #[derive(Clone)]
-pub struct Dca<'a>{
-    client: &'a OpenoceanClient,
+pub struct Dca {
+    client: OpenoceanClient,
 }
 
 
-impl<'a> Dca<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl OpenoceanClient {
+    pub fn dca(&self) -> Dca {
+        Dca::new(self)
+    }
+}
+
+impl Dca {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn create_dca_order(&self, chain: Chain, params: &DcaCreateSwapParams) -> Result<DcaCreateSwapResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.dca.create_dca_order", skip_all, fields(chain = %chain)))]
+    pub async fn create_dca_order(&self, chain: Chain, params: &DcaCreateSwapParams) -> Result<(), OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Dca)?;
+        ensure_chain(&chain, [&params.order_maker, &params.maker_asset, &params.taker_asset, &params.referrer])?;
         let path = format!("/v2/{}/dca/swap", chain);
-        self.client.post(&path, params).await
+        let res: DcaCreateSwapResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn cancel_dca_order(&self, chain: Chain, params: &DcaCancelOrderParams) -> Result<DcaCancelSwapResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.dca.cancel_dca_order", skip_all, fields(chain = %chain)))]
+    pub async fn cancel_dca_order(&self, chain: Chain, params: &DcaCancelOrderParams) -> Result<(), OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Dca)?;
         let path = format!("/v2/{}/dca/cancel", chain);
-        self.client.post(&path, params).await
+        let res: DcaCancelSwapResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_dca_orders(&self, chain: Chain, address: String) -> Result<GetDcaOrdersResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.dca.get_dca_orders", skip_all, fields(chain = %chain)))]
+    pub async fn get_dca_orders(&self, chain: Chain, address: String) -> Result<Vec<DcaOrder>, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Dca)?;
         let path = format!("/v2/{}/dca/address/{}", chain, address);
-        self.client.get_json(&path).await
+        let res: GetDcaOrdersResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_dca_order_fills(&self, chain: Chain, order_hash: String) -> Result<GetDcaOrderFillsResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.dca.get_dca_order_fills", skip_all, fields(chain = %chain)))]
+    pub async fn get_dca_order_fills(&self, chain: Chain, order_hash: String) -> Result<Vec<DcaOrderFill>, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Dca)?;
         let path = format!("/v2/{}/dca/fill/{}", chain, order_hash);
-        self.client.get_json(&path).await
+        let res: GetDcaOrderFillsResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 }
\ No newline at end of file
@@ -1,41 +1,62 @@
-use crate::{models::gasless::{GaslessQuoteParams, GaslessQuoteResponse, GaslessSwapParams, GaslessSwapResponse, GetOrderStatusParams, GetOrderStatusResponse}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::base::Envelope, Chain, Feature, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::gasless::*;
 
 
 
 
 
 #[derive(Clone)]
-pub struct Gasless<'a>{
-    client: &'a OpenoceanClient,
+pub struct Gasless {
+    client: OpenoceanClient,
 }
 
 
+impl OpenoceanClient {
+    pub fn gasless(&self) -> Gasless {
+        Gasless::new(self)
+    }
+}
+
 // #[async_trait]
-impl<'a> Gasless<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl Gasless {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn quote(&self, chain: Chain, params: &GaslessQuoteParams) -> Result<GaslessQuoteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.gasless.quote", skip_all, fields(chain = %chain)))]
+    pub async fn quote(&self, chain: Chain, params: &GaslessQuoteParams) -> Result<GaslessQuoteData, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Gasless)?;
+        let addresses = [&params.in_token_address, &params.out_token_address];
+        ensure_chain(&chain, addresses.into_iter().chain(&params.referrer))?;
         let path = format!("/v4/{}/swap", chain);
-        self.client.get_json_with_query(&path, params).await
+        let res: GaslessQuoteResponse = self.client.get_json_with_query(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn swap(&self, chain: Chain, params: &GaslessSwapParams) -> Result<GaslessSwapResponse, OpenoceanError> {
+    /// Submits a gasless swap and returns the order hash.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.gasless.swap", skip_all, fields(chain = %chain)))]
+    pub async fn swap(&self, chain: Chain, params: &GaslessSwapParams) -> Result<String, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Gasless)?;
+        ensure_chain(&chain, [&params.from, &params.to, &params.in_token, &params.out_token])?;
         let path = format!("/v4/gasless/{}/swap", chain);
-        self.client.post(&path, params).await
+        let res: GaslessSwapResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_order_status(&self, chain: Chain, params: &GetOrderStatusParams) -> Result<GetOrderStatusResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.gasless.get_order_status", skip_all, fields(chain = %chain)))]
+    pub async fn get_order_status(&self, chain: Chain, params: &GetOrderStatusParams) -> Result<GetOrderStatusData, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Gasless)?;
         let path = format!("/v4/gasless/{}/order", chain);
-        self.client.get_json_with_query(&path, params).await
+        let res: GetOrderStatusResponse = self.client.get_json_with_query(&path, params).await?;
+        res.into_result(&path)
     }
     
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{models::gasless::GaslessSwapParams, OpenoceanConfig};
+    use crate::{models::gasless::GaslessSwapParams, types::{Timestamp, U256}, OpenoceanConfig};
 
     use super::*;
 
@@ -45,14 +66,16 @@ mod tests {
         let gasless = Gasless::new(&client);
         let res = gasless.quote(Chain::Bsc, &GaslessQuoteParams {
             chain: "bsc".to_string(),
-            in_token_address: "0x55d398326f99059ff775485246999027b3197955".to_string(),
-            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".to_string(),
-            amount_decimals: "5000000000000000000".to_string(),
-            gas_price_decimals: "1000000000".to_string(),
+            in_token_address: "0x55d398326f99059ff775485246999027b3197955".parse().unwrap(),
+            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".parse().unwrap(),
+            amount_decimals: U256::from(5000000000000000000u64),
+            gas_price_decimals: U256::from(1000000000u64),
             slippage: None,
             referrer: None,
             disabled_dex_ids: None,
         }).await.unwrap();
+
+        println!("quote response: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
     #[tokio::test]
@@ -60,24 +83,24 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let gasless = Gasless::new(&client);
         let res = gasless.swap(Chain::Arbitrum, &GaslessSwapParams {
-            from: "0x72f16Cae8F50Ad615AB5A8e231A496b2ace52532".to_string(),
-            to: "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64".to_string(),
-            data: "0x90411a32000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a5000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000001c000000000000000000000000032eb7902d4134bf98a28b963d26de779af92a212000000000000000000000000af88d065e77c8cc2239327c5edb3a432268e5831000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a5000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000000000000000000000000000000000000000a885300000000000000000000000000000000000000000000000000000000000aa39000000000000000000000000000000000000000000000000000000000000000020000000000000000000000003fe9c9165d3cb5086ce49c9b4a67c01d4e869bfd0000000000000000000000000000000000000000000000000000000000000140000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000104e5b07cdb000000000000000000000000ba1f4c88d563df1f66f726839b0e7e81183ce929000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000470de4df820000000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a500000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000002e32eb7902d4134bf98a28b963d26de779af92a212000bb882af49447d8a07e3bd95bd0d56f35241523fbab100000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000002449f86542200000000000000000000000082af49447d8a07e3bd95bd0d56f35241523fbab100000000000000000000000000000001000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000004400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000104e5b07cdb0000000000000000000000006f38e884725a116c9c7fbf208e79fe8828a2595f00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000002e82af49447d8a07e3bd95bd0d56f35241523fbab1000064af88d065e77c8cc2239327c5edb3a432268e58310000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".to_string(),
-            amount_decimals: "20000000000000000".to_string(),
-            fee_amount1: "23660".to_string(),
-            fee_amount2: "13916340000000000".to_string(),
+            from: "0x72f16Cae8F50Ad615AB5A8e231A496b2ace52532".parse().unwrap(),
+            to: "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64".parse().unwrap(),
+            data: "0x90411a32000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a5000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000001c000000000000000000000000032eb7902d4134bf98a28b963d26de779af92a212000000000000000000000000af88d065e77c8cc2239327c5edb3a432268e5831000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a5000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000000000000000000000000000000000000000a885300000000000000000000000000000000000000000000000000000000000aa39000000000000000000000000000000000000000000000000000000000000000020000000000000000000000003fe9c9165d3cb5086ce49c9b4a67c01d4e869bfd0000000000000000000000000000000000000000000000000000000000000140000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000104e5b07cdb000000000000000000000000ba1f4c88d563df1f66f726839b0e7e81183ce929000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000470de4df820000000000000000000000000000f851d3d46237ec552a4c6e383a973115e781b1a500000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000002e32eb7902d4134bf98a28b963d26de779af92a212000bb882af49447d8a07e3bd95bd0d56f35241523fbab100000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000002449f86542200000000000000000000000082af49447d8a07e3bd95bd0d56f35241523fbab100000000000000000000000000000001000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000004400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000104e5b07cdb0000000000000000000000006f38e884725a116c9c7fbf208e79fe8828a2595f00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000002e82af49447d8a07e3bd95bd0d56f35241523fbab1000064af88d065e77c8cc2239327c5edb3a432268e58310000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".parse().unwrap(),
+            amount_decimals: U256::from(20000000000000000u64),
+            fee_amount1: U256::from(23660u64),
+            fee_amount2: U256::from(13916340000000000u64),
             flag: 2,
-            gas_price_decimals: 10000000,
-            deadline: 1747127367,
-            in_token: "0x32eb7902d4134bf98a28b963d26de779af92a212".to_string(),
-            out_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".to_string(),
+            gas_price_decimals: U256::from(10000000u64),
+            deadline: Timestamp::from_unix_secs(1747127367),
+            in_token: "0x32eb7902d4134bf98a28b963d26de779af92a212".parse().unwrap(),
+            out_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".parse().unwrap(),
             nonce: 0,
-            permit: "0x30f28b7a00000000000000000000000032eb7902d4134bf98a28b963d26de779af92a21200000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000068230c47000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000072f16cae8f50ad615ab5a8e231a496b2ace5253200000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000041480ece65964a49824a41690c6052bf0f26e5ea75554dd72be9b83a8b5a42f1e2075aaac15193296c973ff6e6957beeb395f8dc9f97ee5f9ec585e2fe3bbab0971c00000000000000000000000000000000000000000000000000000000000000".to_string(),
+            permit: "0x30f28b7a00000000000000000000000032eb7902d4134bf98a28b963d26de779af92a21200000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000068230c47000000000000000000000000b1dd8e9ebbf5f150b75642d1653df0dacd0bff4700000000000000000000000000000000000000000000000000470de4df82000000000000000000000000000072f16cae8f50ad615ab5a8e231a496b2ace5253200000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000041480ece65964a49824a41690c6052bf0f26e5ea75554dd72be9b83a8b5a42f1e2075aaac15193296c973ff6e6957beeb395f8dc9f97ee5f9ec585e2fe3bbab0971c00000000000000000000000000000000000000000000000000000000000000".parse().unwrap(),
             usdvaluation: 0.032,
         }).await.unwrap();
 
-        println!("swap response: {}", serde_json::to_string_pretty(&res).unwrap());
-        assert!(res.code == 0);
+        println!("swap order hash: {}", res);
+        assert!(res.starts_with("0x"));
     }
 
     #[tokio::test]
@@ -90,6 +113,6 @@ mod tests {
         }).await.unwrap();
         
         println!("get order status response: {}", serde_json::to_string_pretty(&res).unwrap());
-        assert!(res.code == 0);
+        assert_eq!(res.hash, "0xcd287de3af73d2502755df502e422a5c44e5e4231de54e5243607a29ac2455e4");
     }
 }
\ No newline at end of file
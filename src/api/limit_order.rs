@@ -1,31 +1,50 @@
-use crate::{models::limit_order::{CancelLimitOrderByAddressResponse, CancelLimitOrderParams, CancelLimitOrderResponse, CreateLimitOrderParams, CreateLimitOrderResponse, GetLimitOrdersByAddressParams}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::{base::Envelope, limit_order}, Chain, Feature, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::limit_order::*;
 
 
 
 
 
 #[derive(Clone)]
-pub struct LimitOrder<'a>{
-    client: &'a OpenoceanClient,
+pub struct LimitOrder {
+    client: OpenoceanClient,
+}
+
+impl OpenoceanClient {
+    pub fn limit_order(&self) -> LimitOrder {
+        LimitOrder::new(self)
+    }
 }
 
-impl<'a> LimitOrder<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl LimitOrder {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn create_limit_order(&self, chain: Chain, params: &CreateLimitOrderParams) -> Result<CreateLimitOrderResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.limit_order.create_limit_order", skip_all, fields(chain = %chain)))]
+    pub async fn create_limit_order(&self, chain: Chain, params: &CreateLimitOrderParams) -> Result<(), OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::LimitOrder)?;
+        let addresses = [&params.taker_asset, &params.maker_asset, &params.order_maker];
+        ensure_chain(&chain, addresses.into_iter().chain(&params.referrer))?;
         let path = format!("/v2/{}/limit-order", chain);
-        self.client.post(&path, params).await
+        let res: CreateLimitOrderResponse = self.client.post(&path, params).await?;
+        res.check(&path).map(|_| ())
     }
 
-    pub async fn cancel_limit_order(&self, chain: Chain, params: &CancelLimitOrderParams) -> Result<CancelLimitOrderResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.limit_order.cancel_limit_order", skip_all, fields(chain = %chain)))]
+    pub async fn cancel_limit_order(&self, chain: Chain, params: &CancelLimitOrderParams) -> Result<(), OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::LimitOrder)?;
         let path = format!("/v2/{}/limit-order/cancelLimitOrder", chain);
-        self.client.post(&path, params).await
+        let res: CancelLimitOrderResponse = self.client.post(&path, params).await?;
+        res.check(&path).map(|_| ())
     }
 
-    pub async fn get_limit_orders_by_address(&self, chain: Chain, address: String, params: &GetLimitOrdersByAddressParams) -> Result<CancelLimitOrderByAddressResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.limit_order.get_limit_orders_by_address", skip_all, fields(chain = %chain)))]
+    pub async fn get_limit_orders_by_address(&self, chain: Chain, address: String, params: &GetLimitOrdersByAddressParams) -> Result<Vec<limit_order::LimitOrder>, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::LimitOrder)?;
         let path = format!("/v2/{}/limit-order/address/{}", chain, address);
-        self.client.get_json_with_query(&path, params).await
+        let res: CancelLimitOrderByAddressResponse = self.client.get_json_with_query(&path, params).await?;
+        res.into_result(&path)
     }
 }
\ No newline at end of file
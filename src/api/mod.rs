@@ -5,13 +5,17 @@ pub mod limit_order;
 pub mod zap;
 pub mod sweep_swap;
 pub mod ticket;
+pub mod chains;
 
+// Each module also re-exports its request and response types, e.g.
+// `openocean_sdk::swap::QuoteParams`; only the handles go to the crate root.
 pub use self::{
-    swap::*,
-    gasless::*,
-    dca::*,
-    limit_order::*,
-    zap::*,
-    sweep_swap::*,
-    ticket::*,
+    swap::Swap,
+    gasless::Gasless,
+    dca::Dca,
+    limit_order::LimitOrder,
+    zap::Zap,
+    sweep_swap::SweepSwap,
+    ticket::Ticket,
+    chains::{ChainEntry, Chains, CHAIN_LIST_PATH},
 };
\ No newline at end of file
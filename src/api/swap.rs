@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::{models::swap::{DecodeInputDataResponse, GasPriceResponse, GasResponse, GetDexListResponse, GetTokenListResponse, GetTransactionResponse, QuoteParams, QuoteResponse, ReverseQuoteParams, ReverseQuoteResponse, SwapQuoteParams, SwapQuoteResponse}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::base::Envelope, types::{Bytes, U256}, BestQuote, Chain, OpenoceanClient, OpenoceanError, QuoteEvaluator, RouteAnalysis, RpcClient, SwapExecution};
+
+pub use crate::models::swap::*;
 
 
 
@@ -8,56 +10,89 @@ use crate::{models::swap::{DecodeInputDataResponse, GasPriceResponse, GasRespons
 
 
 #[derive(Clone)]
-pub struct Swap<'a>{
-    client: &'a OpenoceanClient,
+pub struct Swap {
+    client: OpenoceanClient,
 }
 
 
+impl OpenoceanClient {
+    pub fn swap(&self) -> Swap {
+        Swap::new(self)
+    }
+}
+
 // #[async_trait]
-impl<'a> Swap<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl Swap {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn quote(&self, chain: Chain, params: &QuoteParams) -> Result<QuoteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.quote", skip_all, fields(chain = %chain)))]
+    pub async fn quote(&self, chain: Chain, params: &QuoteParams) -> Result<QuoteData, OpenoceanError> {
+        ensure_chain(&chain, [&params.in_token_address, &params.out_token_address])?;
         let path = format!("/v4/{}/quote", chain);
         let res: QuoteResponse = self.client.get_json_with_query(&path, params).await?;
-        Ok(res)
+        res.into_result(&path)
     }
 
-    pub async fn get_token_list(&self, chain: Chain) -> Result<GetTokenListResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.get_token_list", skip_all, fields(chain = %chain)))]
+    pub async fn get_token_list(&self, chain: Chain) -> Result<Vec<Token>, OpenoceanError> {
         let path = format!("/v4/{}/tokenList", chain);
-        self.client.get_json(&path).await
+        let res: GetTokenListResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_price(&self, chain: Chain) -> Result<GasResponse, OpenoceanError> {
+    /// Resolves every token symbol in `route` from the chain's token list,
+    /// for routes from either [`Swap::quote`] or
+    /// [`Gasless::quote`](crate::Gasless::quote).
+    pub async fn resolve_route(&self, chain: Chain, route: &mut RouteAnalysis) -> Result<(), OpenoceanError> {
+        let tokens = self.get_token_list(chain).await?;
+        route.resolve_with_token_list(&tokens);
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.get_price", skip_all, fields(chain = %chain)))]
+    pub async fn get_price(&self, chain: Chain) -> Result<GasPrice, OpenoceanError> {
         let path = format!("/v4/{}/gasPrice", chain);
-        self.client.get_json(&path).await
+        let res: GasResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 
-    pub async fn reverse_quote(&self, chain: Chain, parmas: &ReverseQuoteParams) -> Result<ReverseQuoteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.reverse_quote", skip_all, fields(chain = %chain)))]
+    pub async fn reverse_quote(&self, chain: Chain, parmas: &ReverseQuoteParams) -> Result<ReverseQuoteData, OpenoceanError> {
+        ensure_chain(&chain, [&parmas.in_token_address, &parmas.out_token_address])?;
         let path = format!("/v4/{}/reverseQuote", chain);
-        self.client.get_json_with_query(&path, parmas).await
+        let res: ReverseQuoteResponse = self.client.get_json_with_query(&path, parmas).await?;
+        res.into_result(&path)
     }
 
-    pub async fn swap_quote(&self, chain: Chain, params: &SwapQuoteParams) -> Result<SwapQuoteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.swap_quote", skip_all, fields(chain = %chain)))]
+    pub async fn swap_quote(&self, chain: Chain, params: &SwapQuoteParams) -> Result<SwapQuoteData, OpenoceanError> {
+        let addresses = [&params.in_token_address, &params.out_token_address, &params.account];
+        ensure_chain(&chain, addresses.into_iter().chain(&params.referrer).chain(&params.sender))?;
         let path = format!("/v4/{}/swap", chain);
-        self.client.get_json_with_query(&path, params).await
+        let res: SwapQuoteResponse = self.client.get_json_with_query(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_dex_list(&self, chain: Chain) -> Result<GetDexListResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.get_dex_list", skip_all, fields(chain = %chain)))]
+    pub async fn get_dex_list(&self, chain: Chain) -> Result<Vec<Dex>, OpenoceanError> {
         let path = format!("/v4/{}/dexList", chain);
-        self.client.get_json(&path).await
+        let res: GetDexListResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 
-    pub async fn get_transaction(&self, chain: Chain, hash: String) -> Result<GetTransactionResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.get_transaction", skip_all, fields(chain = %chain)))]
+    pub async fn get_transaction(&self, chain: Chain, hash: String) -> Result<Transaction, OpenoceanError> {
         let mut query = HashMap::new();
         query.insert("hash", hash);
 
         let path = format!("/v4/{}/getTransaction", chain);
-        self.client.get_json_with_query(&path, &query).await
+        let res: GetTransactionResponse = self.client.get_json_with_query(&path, &query).await?;
+        res.into_result(&path)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.decode_input_data", skip_all, fields(chain = %chain)))]
     pub async fn decode_input_data(&self, chain: Chain, data: String, method: String) -> Result<DecodeInputDataResponse, OpenoceanError> {
         let mut query = HashMap::new();
         query.insert("data", data);
@@ -67,15 +102,105 @@ impl<'a> Swap<'a> {
         self.client.get_json_with_query(&path, &query).await
     }
 
-    pub async fn get_gas_price(&self, chain: Chain) -> Result<GasPriceResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.swap.get_gas_price", skip_all, fields(chain = %chain)))]
+    pub async fn get_gas_price(&self, chain: Chain) -> Result<GasPriceData, OpenoceanError> {
         let path = format!("/v4/{}/gasPrice", chain);
-        self.client.get_json(&path).await
+        let res: GasPriceResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
+    }
+
+    /// Broadcasts a signed swap transaction through `rpc`, then waits for it
+    /// like [`Swap::track_transaction`].
+    pub async fn broadcast(&self, rpc: &RpcClient, chain: Chain, raw: &Bytes) -> Result<SwapExecution, OpenoceanError> {
+        let hash = rpc.send_raw_transaction(&chain, raw).await?;
+        self.track_transaction(rpc, chain, &hash).await
+    }
+
+    /// Waits for the receipt of `hash` through `rpc`, then looks the swap up
+    /// with [`Swap::get_transaction`]. The transaction is on chain by then,
+    /// so a failed lookup (usually an unindexed transaction) only leaves
+    /// [`SwapExecution::transaction`] empty instead of losing the receipt.
+    pub async fn track_transaction(&self, rpc: &RpcClient, chain: Chain, hash: &Bytes) -> Result<SwapExecution, OpenoceanError> {
+        let receipt = rpc.wait_for_receipt(&chain, hash).await?;
+        let transaction = self.get_transaction(chain.clone(), hash.to_hex()).await.ok();
+        Ok(SwapExecution { chain, hash: hash.clone(), receipt, transaction })
+    }
+
+    /// Builds a [`QuoteEvaluator`] from the chain's standard gas price and the
+    /// native token's USD price, read from a quote of one native token into
+    /// its wrapped form. Chains without a distinct wrapped native token need
+    /// [`QuoteEvaluator::new`] instead.
+    pub async fn quote_evaluator(&self, chain: Chain) -> Result<QuoteEvaluator, OpenoceanError> {
+        let info = chain.info().ok_or_else(|| OpenoceanError::InvalidParams(format!("no native token metadata for chain {chain}")))?;
+        let (native, wrapped) = match (info.native_address, info.wrapped_native) {
+            (Some(native), Some(wrapped)) if !native.eq_ignore_ascii_case(wrapped) => (native, wrapped),
+            _ => return Err(OpenoceanError::InvalidParams(format!("chain {chain} has no wrapped native token to price gas with"))),
+        };
+
+        let gas_price = match self.get_gas_price(chain.clone()).await? {
+            GasPriceData::Evm(gas) => gas.standard.legacy_gas_price,
+            GasPriceData::NonEvm(gas) => gas.standard,
+        };
+        let params = QuoteParams::builder()
+            .in_token_address(native)
+            .out_token_address(wrapped)
+            .amount_decimals(U256::exp10(info.native_decimals as u32).unwrap_or(U256::MAX))
+            .gas_price_decimals(gas_price)
+            .build()?;
+        let quote = self.quote(chain, &params).await?;
+        let native_usd = quote.in_token.usd.trim().parse::<f64>().map_err(|e| {
+            OpenoceanError::Internal(format!("quote has no usd price for {}: {e}", quote.in_token.symbol))
+        })?;
+
+        Ok(QuoteEvaluator::new(gas_price, info.native_decimals, native_usd))
+    }
+
+    /// Fires every variant concurrently and returns the one with the highest
+    /// output net of gas. Variants that fail are skipped; the last error is
+    /// returned only if none succeed. All variants must share an output token.
+    pub async fn best_quote<I>(&self, chain: Chain, evaluator: &QuoteEvaluator, variants: I) -> Result<BestQuote<QuoteData>, OpenoceanError>
+    where
+        I: IntoIterator<Item = QuoteParams>,
+    {
+        let variants: Vec<QuoteParams> = variants.into_iter().collect();
+        let Some(first) = variants.first() else {
+            return Err(OpenoceanError::InvalidParams("best_quote needs at least one variant".into()));
+        };
+        if let Some(other) = variants.iter().find(|v| v.out_token_address != first.out_token_address) {
+            return Err(OpenoceanError::InvalidParams(format!(
+                "best_quote variants must share an output token, got {} and {}",
+                first.out_token_address, other.out_token_address
+            )));
+        }
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (index, params) in variants.into_iter().enumerate() {
+            let (swap, chain) = (self.clone(), chain.clone());
+            tasks.spawn(async move { (index, swap.quote(chain, &params).await) });
+        }
+
+        let mut quotes = Vec::new();
+        let mut last_err = None;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, Ok(quote))) => quotes.push((index, quote)),
+                Ok((_, Err(e))) => last_err = Some(e),
+                Err(e) => last_err = Some(OpenoceanError::Internal(format!("quote task failed: {e}"))),
+            }
+        }
+        // Completion order is arbitrary; evaluate in variant order so ties are stable.
+        quotes.sort_by_key(|(index, _)| *index);
+
+        match evaluator.best(quotes)? {
+            Some(best) => Ok(best),
+            None => Err(last_err.unwrap_or_else(|| OpenoceanError::Internal("no quotes returned".into()))),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::OpenoceanConfig;
+    use crate::{types::{Slippage, U256}, OpenoceanConfig};
 
     use super::*;
 
@@ -84,24 +209,62 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.quote(Chain::Bsc, &QuoteParams {
-            in_token_address: "0x55d398326f99059ff775485246999027b3197955".to_string(),
-            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".to_string(),
-            amount_decimals: "5000000000000000000".to_string(),
-            gas_price_decimals: "1000000000".to_string(),
+            in_token_address: "0x55d398326f99059ff775485246999027b3197955".parse().unwrap(),
+            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".parse().unwrap(),
+            amount_decimals: U256::from(5000000000000000000u64),
+            gas_price_decimals: U256::from(1000000000u64),
             slippage: None,
             disabled_dex_ids: None,
             enabled_dex_ids: None,
         }).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert_eq!(res.in_token.symbol, "USDT");
         println!("quote: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
+    #[tokio::test]
+    async fn test_best_quote_skips_a_bad_variant() {
+        let transport = std::sync::Arc::new(crate::MockTransport::new());
+        let client = OpenoceanClient::with_transport(OpenoceanConfig::default(), transport.clone()).unwrap();
+        // an unpriced output token can't be evaluated
+        let quote = |out_amount: &str, usd: &str| {
+            let token = serde_json::json!({
+                "address": "0x55d398326f99059ff775485246999027b3197955",
+                "decimals": 6, "symbol": "USDT", "name": "USDT", "usd": usd, "volume": 0
+            });
+            serde_json::json!({ "code": 200, "data": {
+                "inToken": token, "outToken": token, "inAmount": "1000000000", "outAmount": out_amount,
+                "estimatedGas": "100000",
+                "path": { "from": "0x55d398326f99059ff775485246999027b3197955", "to": "0x55d398326f99059ff775485246999027b3197955", "parts": 1, "routes": [] },
+                "save": 0, "price_impact": "0.01%", "exchange": "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64"
+            }})
+        };
+        transport
+            .on_json(reqwest::Method::GET, "/v4/bsc/quote", quote("2000000000", ""))
+            .on_json(reqwest::Method::GET, "/v4/bsc/quote", quote("990000000", "1"))
+            .on_json(reqwest::Method::GET, "/v4/bsc/quote", quote("1000000000", "1"));
+
+        let variants = [1u64, 2, 3].map(|gwei| {
+            QuoteParams::builder()
+                .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+                .out_token_address("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+                .amount_decimals("1000000000")
+                .gas_price_decimals((gwei * 1_000_000_000).to_string())
+                .build()
+                .unwrap()
+        });
+        let evaluator = QuoteEvaluator::new(1_000_000_000u64, 18, 3000.0);
+        let best = Swap::new(&client).best_quote(Chain::Bsc, &evaluator, variants).await.unwrap();
+
+        assert_eq!(best.quote.out_amount, U256::from(1_000_000_000u64));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
     #[tokio::test]
     async fn test_get_token_list() {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.get_token_list(Chain::Bsc).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert!(!res.is_empty());
         println!("token list: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
@@ -110,7 +273,7 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.get_price(Chain::Bsc).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert!(res.standard > 0.0);
         println!("gas price: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
@@ -119,17 +282,16 @@ mod tests {
     async fn test_reverse_quote() {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
-        let res = swap.reverse_quote(Chain::Bsc, &&ReverseQuoteParams {
-            in_token_address: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".to_string(),
-            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".to_string(),
-            gas_price: 1.to_string(),
-            amount: 1.to_string(),
+        let res = swap.reverse_quote(Chain::Bsc, &ReverseQuoteParams {
+            in_token_address: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse().unwrap(),
+            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".parse().unwrap(),
+            gas_price: U256::from(1u64),
+            amount: U256::from(1u64),
             slippage: None,
             disabled_dex_ids: None,
             enabled_dex_ids: None,
         }).await.unwrap();
 
-        assert_eq!(res.code, 200);
         println!("reverse quote: {}", serde_json::to_string_pretty(&res).unwrap());        
     }
 
@@ -138,20 +300,20 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.swap_quote(Chain::Bsc, &SwapQuoteParams {
-            in_token_address: "0x55d398326f99059ff775485246999027b3197955".to_string(),
-            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".to_string(),
-            amount_decimals: "5000000000000000000".to_string(),
-            gas_price_decimals: "1000000000".to_string(),
-            slippage: Some("1".to_string()),
-            account: "0x9116780aEf4B376499358fa7dEeC00cCF64fA801".to_string(),
-            referrer: Some("0xD4eb4cbB1ECbf96a1F0C67D958Ff6fBbB7B037BB".to_string()),
+            in_token_address: "0x55d398326f99059ff775485246999027b3197955".parse().unwrap(),
+            out_token_address: "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d".parse().unwrap(),
+            amount_decimals: U256::from(5000000000000000000u64),
+            gas_price_decimals: U256::from(1000000000u64),
+            slippage: Some(Slippage::DEFAULT),
+            account: "0x9116780aEf4B376499358fa7dEeC00cCF64fA801".parse().unwrap(),
+            referrer: Some("0xD4eb4cbB1ECbf96a1F0C67D958Ff6fBbB7B037BB".parse().unwrap()),
             referrer_fee: None,
             disabled_dex_ids: None,
             enabled_dex_ids: None,
             sender: None,
             mint_output: None,
         }).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert_eq!(res.chain_id, 56);
         println!("swap quote: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
@@ -160,7 +322,7 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.get_dex_list(Chain::Bsc).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert!(!res.is_empty());
         println!("dex list: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
@@ -169,7 +331,7 @@ mod tests {
         let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.get_transaction(Chain::Bsc, "0x756b98a89714be5c640ea9922aba12e0c94bc30e5a17e111d1aa40373cc24782".to_string()).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert_eq!(res.tx_hash, "0x756b98a89714be5c640ea9922aba12e0c94bc30e5a17e111d1aa40373cc24782");
         println!("transaction: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 
@@ -186,11 +348,10 @@ mod tests {
         let client = OpenoceanClient::new(crate::OpenoceanConfig::default()).unwrap();
         let swap = Swap::new(&client);
         let res = swap.get_gas_price(Chain::Bsc).await.unwrap();
-        assert_eq!(res.code, 200);
         println!("gas price: {}", serde_json::to_string_pretty(&res).unwrap());
 
         let res = swap.get_gas_price(Chain::Eth).await.unwrap();
-        assert_eq!(res.code, 200);
+        assert!(matches!(res, GasPriceData::Evm(_)));
         println!("gas price: {}", serde_json::to_string_pretty(&res).unwrap());
     }
 }
\ No newline at end of file
@@ -1,19 +1,31 @@
-use crate::{models::{sweep_swap::{MultiSwapQuoteParams, MultiSwapQuoteResponse}}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, Chain, Feature, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::sweep_swap::*;
 
 
 
 
 #[derive(Clone)]
-pub struct SweepSwap<'a>{
-    client: &'a OpenoceanClient,
+pub struct SweepSwap {
+    client: OpenoceanClient,
+}
+
+impl OpenoceanClient {
+    pub fn sweep_swap(&self) -> SweepSwap {
+        SweepSwap::new(self)
+    }
 }
 
-impl<'a> SweepSwap<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl SweepSwap {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.sweep_swap.multi_swap_quote", skip_all, fields(chain = %chain)))]
     pub async fn multi_swap_quote(&self, chain: Chain, params: &MultiSwapQuoteParams) -> Result<MultiSwapQuoteResponse, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::SweepSwap)?;
+        let addresses = [&params.account, &params.in_token.in_token_address, &params.out_token.out_token_address];
+        ensure_chain(&chain, addresses.into_iter().chain(&params.referrer))?;
         let path = format!("/{}/multi_swap_route", chain);
         self.client.post(&path, params).await
     }
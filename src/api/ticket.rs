@@ -1,28 +1,48 @@
-use crate::{models::ticket::{GetTicketResponse, SubmitTicketParams, SubmitTicketResponse}, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::base::Envelope, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::ticket::*;
 
 
 
 
 #[derive(Clone)]
-pub struct Ticket<'a>{
-    client: &'a OpenoceanClient,
+pub struct Ticket {
+    client: OpenoceanClient,
+}
+
+impl OpenoceanClient {
+    pub fn ticket(&self) -> Ticket {
+        Ticket::new(self)
+    }
 }
 
-impl<'a> Ticket<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl Ticket {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn submit(&self, referer: String, params: &SubmitTicketParams) -> Result<SubmitTicketResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.ticket.submit", skip_all))]
+    pub async fn submit(&self, referer: String, params: &SubmitTicketParams) -> Result<SubmitTicketData, OpenoceanError> {
+        let addresses = [
+            &params.account,
+            &params.transaction.from,
+            &params.transaction.to,
+            &params.quote.in_token_address,
+            &params.quote.out_token_address,
+        ];
+        ensure_chain(&params.chain, addresses)?;
         let path = format!("/{}/ticket", referer);
-        self.client.post(&path, params).await
+        let res: SubmitTicketResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.ticket.get_ticket", skip_all))]
     pub async fn get_ticket(
         &self,
         referer: String,
-    ) -> Result<GetTicketResponse, OpenoceanError> {
+    ) -> Result<GetTicketData, OpenoceanError> {
         let path = format!("/{}/ticket", referer);
-        self.client.get_json(&path).await
+        let res: GetTicketResponse = self.client.get_json(&path).await?;
+        res.into_result(&path)
     }
 }
\ No newline at end of file
@@ -1,25 +1,41 @@
-use crate::{models::zap::{BuildRouteParams, BuildRouteResponse, RouteParams, RouteResponse}, Chain, OpenoceanClient, OpenoceanError};
+use crate::{address::ensure_chain, models::base::Envelope, Chain, Feature, OpenoceanClient, OpenoceanError};
+
+pub use crate::models::zap::*;
 
 
 
 
 #[derive(Clone)]
-pub struct Zap<'a>{
-    client: &'a OpenoceanClient,
+pub struct Zap {
+    client: OpenoceanClient,
+}
+
+impl OpenoceanClient {
+    pub fn zap(&self) -> Zap {
+        Zap::new(self)
+    }
 }
 
-impl<'a> Zap<'a> {
-    pub fn new(client: &'a OpenoceanClient) -> Self {
-        Self { client }
+impl Zap {
+    pub fn new(client: &OpenoceanClient) -> Self {
+        Self { client: client.clone() }
     }
 
-    pub async fn route(&self, chain: Chain, params: &RouteParams) -> Result<RouteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.zap.route", skip_all, fields(chain = %chain)))]
+    pub async fn route(&self, chain: Chain, params: &RouteParams) -> Result<RouteData, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Zap)?;
+        ensure_chain(&chain, params.tokens.iter().map(|t| &t.token).chain(&params.referrer))?;
         let path = format!("/zap/{}/in/route", chain);
-        self.client.post(&path, params).await
+        let res: RouteResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 
-    pub async fn build_route(&self, chain: Chain, params: &BuildRouteParams) -> Result<BuildRouteResponse, OpenoceanError> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "openocean.zap.build_route", skip_all, fields(chain = %chain)))]
+    pub async fn build_route(&self, chain: Chain, params: &BuildRouteParams) -> Result<BuildRouteData, OpenoceanError> {
+        self.client.ensure_supported(&chain, Feature::Zap)?;
+        ensure_chain(&chain, [&params.account])?;
         let path = format!("/zap/{}/in/route/build", chain);
-        self.client.post(&path, params).await
+        let res: BuildRouteResponse = self.client.post(&path, params).await?;
+        res.into_result(&path)
     }
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::fmt;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+
+
+/// Header used by the OpenOcean Pro API to carry the API key.
+pub const DEFAULT_API_KEY_HEADER: &str = "apikey";
+
+const REDACTED: &str = "***";
+
+/// An OpenOcean Pro API key.
+///
+/// `Debug` and `Display` never print the key itself; use [`ApiKey::expose`]
+/// where the raw value is needed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Replaces every occurrence of the key in `text`.
+    pub(crate) fn redact(&self, text: &str) -> String {
+        if self.0.is_empty() {
+            text.to_string()
+        } else {
+            text.replace(&self.0, REDACTED)
+        }
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self(key.to_string())
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiKey({REDACTED})")
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// `Debug` view of request headers that hides credentials: the API key
+/// header, `authorization`, cookies, and anything naming a key, token or secret.
+pub(crate) struct RedactedHeaders<'a> {
+    pub headers: &'a HeaderMap,
+    pub api_key_header: &'a str,
+}
+
+impl RedactedHeaders<'_> {
+    fn is_sensitive(&self, name: &HeaderName, value: &HeaderValue) -> bool {
+        let name = name.as_str();
+        value.is_sensitive()
+            || name.eq_ignore_ascii_case(self.api_key_header)
+            || matches!(name, "authorization" | "proxy-authorization" | "cookie")
+            || ["key", "token", "secret"].iter().any(|s| name.contains(s))
+    }
+}
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.headers.iter().map(|(name, value)| {
+                let shown = if self.is_sensitive(name, value) { REDACTED } else { value.to_str().unwrap_or("<binary>") };
+                (name.as_str(), shown)
+            }))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_is_redacted() {
+        let key = ApiKey::new("secret-key-123");
+        assert_eq!(format!("{key:?}"), "ApiKey(***)");
+        assert_eq!(key.to_string(), "***");
+        assert_eq!(key.redact(r#"{"error":"invalid key secret-key-123"}"#), r#"{"error":"invalid key ***"}"#);
+    }
+
+    #[test]
+    fn test_headers_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-openocean-auth", HeaderValue::from_static("secret-1"));
+        headers.insert("authorization", HeaderValue::from_static("Bearer secret-2"));
+        headers.insert("x-access-token", HeaderValue::from_static("secret-3"));
+        headers.insert("x-request-source", HeaderValue::from_static("sdk"));
+
+        let shown = format!("{:?}", RedactedHeaders { headers: &headers, api_key_header: "X-OpenOcean-Auth" });
+        assert!(!shown.contains("secret"), "{shown}");
+        assert!(shown.contains(r#""x-request-source": "sdk""#), "{shown}");
+    }
+}
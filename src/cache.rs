@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::{models::base::{is_success_code, EnvelopeStatus}, rate_limit::{matches_prefix, segments}, HttpResponse};
+
+
+
+/// Response cache settings for [`OpenoceanConfig`](crate::OpenoceanConfig).
+///
+/// Only successful GET responses whose path matches one of `ttls` are cached,
+/// keyed by the full url including the query. Patterns follow the same rules
+/// as [`RateLimitConfig`](crate::RateLimitConfig). With no TTLs the cache is off.
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    pub ttls: Vec<(String, Duration)>,
+    /// Upper bound on cached responses; the least recently used one is evicted.
+    pub max_entries: usize,
+    /// How long past its TTL an entry is still served while a background
+    /// request refreshes it.
+    pub stale_while_revalidate: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttls: Vec::new(),
+            max_entries: 1024,
+            stale_while_revalidate: Duration::ZERO,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Caches token and DEX lists for an hour and gas prices for 5 seconds.
+    pub fn recommended() -> Self {
+        Self {
+            ttls: vec![
+                ("/v4/{chain}/tokenList".to_string(), Duration::from_secs(3600)),
+                ("/v4/{chain}/dexList".to_string(), Duration::from_secs(3600)),
+                ("/v4/{chain}/gasPrice".to_string(), Duration::from_secs(5)),
+            ],
+            stale_while_revalidate: Duration::from_secs(10),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    path: String,
+    response: HttpResponse,
+    expires_at: Instant,
+    last_used: Instant,
+    refreshing: bool,
+}
+
+pub(crate) enum Lookup {
+    Fresh(HttpResponse),
+    /// Expired but within the stale-while-revalidate window. `refresh` is set
+    /// for exactly one caller, which is expected to refresh the entry.
+    Stale { response: HttpResponse, refresh: bool },
+    Miss,
+}
+
+/// In-memory cache of raw responses shared by every clone of a client.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    patterns: Vec<(Vec<String>, Duration)>,
+    max_entries: usize,
+    stale_while_revalidate: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl ResponseCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            patterns: config
+                .ttls
+                .iter()
+                .map(|(pattern, ttl)| (segments(pattern).map(str::to_string).collect(), *ttl))
+                .collect(),
+            max_entries: config.max_entries,
+            stale_while_revalidate: config.stale_while_revalidate,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// TTL for responses of `path`, `None` if they are not cached.
+    pub fn ttl(&self, path: &str) -> Option<Duration> {
+        if self.max_entries == 0 {
+            return None;
+        }
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| matches_prefix(pattern, path))
+            .map(|(_, ttl)| *ttl)
+    }
+
+    pub fn get(&self, key: &str) -> Lookup {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+
+        let Some(entry) = entries.get_mut(key) else {
+            return Lookup::Miss;
+        };
+        if now < entry.expires_at {
+            entry.last_used = now;
+            return Lookup::Fresh(entry.response.clone());
+        }
+        if now < entry.expires_at + self.stale_while_revalidate {
+            entry.last_used = now;
+            let refresh = !entry.refreshing;
+            entry.refreshing = true;
+            return Lookup::Stale { response: entry.response.clone(), refresh };
+        }
+
+        entries.remove(key);
+        Lookup::Miss
+    }
+
+    /// Stores `response` if it is a success, both at HTTP and envelope level.
+    pub fn store(&self, key: &str, path: &str, ttl: Duration, response: &HttpResponse) {
+        let mut entries = self.entries.lock().unwrap();
+
+        if !is_cacheable(response) {
+            // let the next stale hit try again
+            if let Some(entry) = entries.get_mut(key) {
+                entry.refreshing = false;
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        if !entries.contains_key(key) && entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            key.to_string(),
+            Entry {
+                path: path.to_string(),
+                response: response.clone(),
+                expires_at: now + ttl,
+                last_used: now,
+                refreshing: false,
+            },
+        );
+    }
+
+    pub fn refresh_failed(&self, key: &str) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.refreshing = false;
+        }
+    }
+
+    /// Drops entries whose path matches `pattern`.
+    pub fn invalidate(&self, pattern: &str) {
+        let pattern: Vec<String> = segments(pattern).map(str::to_string).collect();
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, entry| !matches_prefix(&pattern, &entry.path));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+fn is_cacheable(response: &HttpResponse) -> bool {
+    response.is_success()
+        && serde_json::from_slice::<EnvelopeStatus>(&response.body)
+            .map(|status| is_success_code(status.code))
+            .unwrap_or(true)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(max_entries: usize) -> ResponseCache {
+        ResponseCache::new(&CacheConfig {
+            ttls: vec![("/v4/{chain}/dexList".to_string(), Duration::from_secs(60))],
+            max_entries,
+            stale_while_revalidate: Duration::from_secs(10),
+        })
+    }
+
+    fn ok() -> HttpResponse {
+        HttpResponse::new(200, r#"{"code":200,"data":[]}"#)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fresh_stale_and_expired() {
+        let cache = cache(8);
+        let ttl = cache.ttl("/v4/bsc/dexList").unwrap();
+        assert!(cache.ttl("/v4/bsc/quote").is_none());
+
+        cache.store("bsc", "/v4/bsc/dexList", ttl, &ok());
+        assert!(matches!(cache.get("bsc"), Lookup::Fresh(_)));
+
+        tokio::time::advance(Duration::from_secs(65)).await;
+        assert!(matches!(cache.get("bsc"), Lookup::Stale { refresh: true, .. }));
+        assert!(matches!(cache.get("bsc"), Lookup::Stale { refresh: false, .. }));
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(matches!(cache.get("bsc"), Lookup::Miss));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bounds_errors_and_invalidation() {
+        let cache = cache(2);
+        let ttl = Duration::from_secs(60);
+
+        cache.store("bsc", "/v4/bsc/dexList", ttl, &ok());
+        cache.store("eth", "/v4/eth/dexList", ttl, &HttpResponse::new(200, r#"{"code":500,"data":null}"#));
+        assert!(matches!(cache.get("eth"), Lookup::Miss));
+
+        cache.store("eth", "/v4/eth/dexList", ttl, &ok());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        cache.get("bsc");
+        cache.store("base", "/v4/base/dexList", ttl, &ok());
+        assert!(matches!(cache.get("eth"), Lookup::Miss));
+        assert!(matches!(cache.get("bsc"), Lookup::Fresh(_)));
+
+        cache.invalidate("/v4/bsc/dexList");
+        assert!(matches!(cache.get("bsc"), Lookup::Miss));
+        assert!(matches!(cache.get("base"), Lookup::Fresh(_)));
+    }
+}
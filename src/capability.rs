@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Chain, ChainFamily, OpenoceanError};
+
+
+
+/// An OpenOcean API family that is only available on some chains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Swap,
+    Gasless,
+    Dca,
+    LimitOrder,
+    Zap,
+    SweepSwap,
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Feature::Swap => "swap",
+            Feature::Gasless => "gasless",
+            Feature::Dca => "dca",
+            Feature::LimitOrder => "limit orders",
+            Feature::Zap => "zap",
+            Feature::SweepSwap => "sweep swap",
+        };
+        f.write_str(s)
+    }
+}
+
+impl Chain {
+    /// Whether OpenOcean offers `feature` on this chain, per the SDK's built-in
+    /// table. Unknown chains are assumed to support everything.
+    ///
+    /// The table follows the OpenOcean docs at release time; use
+    /// [`Capabilities`] to correct it without waiting for a new version.
+    pub fn supports(&self, feature: Feature) -> bool {
+        use Chain::*;
+
+        match feature {
+            Feature::Swap => true,
+            _ if matches!(self, Unknown(_)) => true,
+            _ if self.family() != Some(ChainFamily::Evm) => false,
+            Feature::Gasless => matches!(
+                self,
+                Eth | Bsc | Polygon | Base | Linea | Arbitrum | Optimism | Avalanche | Sonic | Berachain | UniChain
+            ),
+            Feature::Dca => matches!(
+                self,
+                Eth | Bsc | Polygon | Base | Linea | Arbitrum | Optimism | Avalanche | Fantom | Sonic | Berachain
+            ),
+            Feature::LimitOrder => matches!(
+                self,
+                Eth | Bsc | Polygon | Base | Linea | Arbitrum | Optimism | Avalanche | Fantom | Gnosis | Cronos
+                    | ZkSyncEra | OpBNB | Kava | Scroll | Blast | Mode | Mantle | Manta | Telos | Sonic | Berachain
+            ),
+            Feature::Zap => matches!(self, Eth | Bsc | Base | Arbitrum | Sonic | Berachain),
+            Feature::SweepSwap => matches!(
+                self,
+                Eth | Bsc | Polygon | Base | Linea | Arbitrum | Optimism | Sonic
+            ),
+        }
+    }
+}
+
+/// Overrides of the built-in capability table, set on
+/// [`OpenoceanConfig`](crate::OpenoceanConfig) or at runtime through
+/// [`OpenoceanClient::set_capability`](crate::OpenoceanClient::set_capability).
+#[derive(Clone, Debug, Default)]
+pub struct Capabilities {
+    overrides: HashMap<(Chain, Feature), bool>,
+}
+
+impl Capabilities {
+    pub fn set(&mut self, chain: Chain, feature: Feature, enabled: bool) {
+        self.overrides.insert((chain, feature), enabled);
+    }
+
+    /// Drops an override, falling back to the built-in table.
+    pub fn reset(&mut self, chain: &Chain, feature: Feature) {
+        self.overrides.remove(&(chain.clone(), feature));
+    }
+
+    pub fn supports(&self, chain: &Chain, feature: Feature) -> bool {
+        self.overrides
+            .get(&(chain.clone(), feature))
+            .copied()
+            .unwrap_or_else(|| chain.supports(feature))
+    }
+
+    pub(crate) fn check(&self, chain: &Chain, feature: Feature) -> Result<(), OpenoceanError> {
+        if self.supports(chain, feature) {
+            Ok(())
+        } else {
+            Err(OpenoceanError::UnsupportedChain { chain: chain.clone(), feature })
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_table_and_overrides() {
+        assert!(Chain::Near.supports(Feature::Swap));
+        assert!(!Chain::Near.supports(Feature::Gasless));
+        assert!(Chain::Bsc.supports(Feature::LimitOrder));
+        assert!(Chain::Unknown("newchain".into()).supports(Feature::Zap));
+
+        let mut caps = Capabilities::default();
+        assert!(matches!(
+            caps.check(&Chain::Near, Feature::Gasless),
+            Err(OpenoceanError::UnsupportedChain { chain: Chain::Near, feature: Feature::Gasless })
+        ));
+
+        caps.set(Chain::Near, Feature::Gasless, true);
+        caps.set(Chain::Bsc, Feature::LimitOrder, false);
+        assert!(caps.check(&Chain::Near, Feature::Gasless).is_ok());
+        assert!(!caps.supports(&Chain::Bsc, Feature::LimitOrder));
+
+        caps.reset(&Chain::Bsc, Feature::LimitOrder);
+        assert!(caps.supports(&Chain::Bsc, Feature::LimitOrder));
+    }
+}
@@ -1,14 +1,20 @@
 use std::fmt;
 use std::convert::TryFrom;
+use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::OpenoceanError;
 
 // https://apis.openocean.finance/developer/apis/supported-chains
 
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// A chain supported by OpenOcean.
+///
+/// Serializes as its API code (`"bsc"`), the same string `Display` prints, and
+/// parses from a code or an EVM chain id. Codes the SDK does not know yet
+/// become [`Chain::Unknown`], so new chains can be used before a release adds them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Chain {
     Eth,
     Bsc,
@@ -56,130 +62,154 @@ pub enum Chain {
     Near,
     Starknet,
     // Sui,
+
+    /// A chain code (or chain id) this version of the SDK has no variant for.
+    Unknown(String),
+}
+
+impl Chain {
+    /// Every chain known to the SDK, i.e. all variants but `Unknown`.
+    pub const ALL: [Chain; 43] = [
+        Chain::Eth, Chain::Bsc, Chain::ZkSyncEra, Chain::Polygon, Chain::Base, Chain::Linea,
+        Chain::Fantom, Chain::Avalanche, Chain::Arbitrum, Chain::Optimism, Chain::Moonriver,
+        Chain::Aurora, Chain::Cronos, Chain::Harmony, Chain::Kava, Chain::MetisAndromeda,
+        Chain::Celo, Chain::Telos, Chain::PolygonZkEVM, Chain::Gnosis, Chain::OpBNB,
+        Chain::Mantle, Chain::Manta, Chain::Scroll, Chain::Blast, Chain::Mode, Chain::Rootstock,
+        Chain::Sei, Chain::Gravity, Chain::Apechain, Chain::Sonic, Chain::Berachain,
+        Chain::MonadTestnet, Chain::UniChain, Chain::Flare, Chain::Swell, Chain::HyperEVM,
+        Chain::Plume, Chain::TAC,
+        Chain::Solana, Chain::Ontology, Chain::Near, Chain::Starknet,
+    ];
 }
 
 impl fmt::Display for Chain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Chain::Eth => "eth",
-            Chain::Bsc => "bsc",
-            Chain::ZkSyncEra => "zksync",
-            Chain::Polygon => "polygon",
-            Chain::Base => "base",
-            Chain::Linea => "linea",
-            Chain::Fantom => "fantom",
-            Chain::Avalanche => "avax",
-            Chain::Arbitrum => "arbitrum",
-            Chain::Optimism => "optimism",
-            Chain::Moonriver => "moonriver",
-            Chain::Aurora => "aurora",
-            Chain::Cronos => "cronos",
-            Chain::Harmony => "harmony",
-            Chain::Kava => "kava",
-            Chain::MetisAndromeda => "metis",
-            Chain::Celo => "celo",
-            Chain::Telos => "telos",
-            Chain::PolygonZkEVM => "polygon_zkevm",
-            Chain::Gnosis => "gnosis",
-            Chain::OpBNB => "opbnb",
-            Chain::Mantle => "mantle",
-            Chain::Manta => "manta",
-            Chain::Scroll => "scroll",
-            Chain::Blast => "blast",
-            Chain::Mode => "mode",
-            Chain::Rootstock => "rootstock",
-            Chain::Sei => "sei",
-            Chain::Gravity => "gravity",
-            Chain::Apechain => "ape",
-            Chain::Sonic => "sonic",
-            Chain::Berachain => "bera",
-            Chain::MonadTestnet => "monad",
-            Chain::UniChain => "uni",
-            Chain::Flare => "flare",
-            Chain::Swell => "swell",
-            Chain::HyperEVM => "hyperevm",
-            Chain::Plume => "plume",
-            Chain::TAC => "tac",
-            // no evm chains
-            Chain::Solana => "solana",
-            Chain::Ontology => "ont",
-            Chain::Near => "near",
-            Chain::Starknet => "starknet",
-            // Sui => "sui",
-        };
-        f.write_str(s)
+        f.write_str(self.code())
     }
 }
 
 
+impl FromStr for Chain {
+    type Err = OpenoceanError;
+
+    /// Accepts an OpenOcean code (`bsc`), an EVM chain id (`56`) or a variant
+    /// name (`Bsc`). Unrecognised codes and ids become [`Chain::Unknown`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Ok(id) = s.parse::<u64>() {
+            return Ok(Chain::from_chain_id(id).unwrap_or_else(|| Chain::Unknown(s.to_string())));
+        }
+
+        let code = s.to_ascii_lowercase();
+        if let Some(chain) = Chain::from_code(&code) {
+            return Ok(chain);
+        }
+        // variant names, as serialized by earlier versions
+        if let Some(chain) = Chain::ALL.into_iter().find(|c| format!("{c:?}").eq_ignore_ascii_case(s)) {
+            return Ok(chain);
+        }
+
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(OpenoceanError::InvalidParams(format!("invalid chain: {s:?}")));
+        }
+        Ok(Chain::Unknown(code))
+    }
+}
+
 impl TryFrom<String> for Chain {
     type Error = OpenoceanError;
 
     fn try_from(chain: String) -> Result<Self, Self::Error> {
-        match chain.as_str() {
-            "1" | "eth" => Ok(Chain::Eth),
-            "56" | "bsc" => Ok(Chain::Bsc),
-            "137" | "polygon" => Ok(Chain::Polygon),
-            "42161" | "arbitrum" => Ok(Chain::Arbitrum),
-            "10" | "optimism" => Ok(Chain::Optimism),
-            "250" | "fantom" => Ok(Chain::Fantom),
-            "43114" | "avalanche" => Ok(Chain::Avalanche),
-            "100" | "gnosis" => Ok(Chain::Gnosis),
-            "59144" | "cronos" => Ok(Chain::Cronos),
-            "1666600000" | "harmony" => Ok(Chain::Harmony),
-            "2000" | "kava" => Ok(Chain::Kava),
-            "1088" | "metis" => Ok(Chain::MetisAndromeda),
-            "42220" | "celo" => Ok(Chain::Celo),
-            "42261" | "telos" => Ok(Chain::Telos),
-            "1313161554" | "polygon_zkevm" => Ok(Chain::PolygonZkEVM),
-            "500" | "opbnb" => Ok(Chain::OpBNB),
-            "501" | "mantle" => Ok(Chain::Mantle),
-            "502" | "manta" => Ok(Chain::Manta),
-            "503" | "scroll" => Ok(Chain::Scroll),
-            "504" | "blast" => Ok(Chain::Blast),
-            "505" | "mode" => Ok(Chain::Mode),
-            "506" | "rootstock" => Ok(Chain::Rootstock),
-            "507" | "sei" => Ok(Chain::Sei),
-            "508" | "gravity" => Ok(Chain::Gravity),
-            "509" | "ape" => Ok(Chain::Apechain),
-            "510" | "sonic" => Ok(Chain::Sonic),
-            "511" | "bera" => Ok(Chain::Berachain),
-            _ => Err(OpenoceanError::Internal(format!("Unsupported chain: {}", chain))),
-        }
+        chain.parse()
+    }
+}
+
+impl TryFrom<&str> for Chain {
+    type Error = OpenoceanError;
+
+    fn try_from(chain: &str) -> Result<Self, Self::Error> {
+        chain.parse()
     }
 }
 
 impl From<Chain> for i32 {
+    /// The EVM chain id, `0` for non-EVM and unknown chains.
     fn from(chain: Chain) -> Self {
-        match chain {
-            Chain::Eth => 1,
-            Chain::Bsc => 56,
-            Chain::Polygon => 137,
-            Chain::Arbitrum => 42161,
-            Chain::Optimism => 10,
-            Chain::Fantom => 250,
-            Chain::Avalanche => 43114,
-            Chain::Gnosis => 100,
-            Chain::Cronos => 59144,
-            Chain::Harmony => 1666600000,
-            Chain::Kava => 2000,
-            Chain::MetisAndromeda => 1088,
-            Chain::Celo => 42220,
-            Chain::Telos => 42261,
-            Chain::PolygonZkEVM => 1313161554,
-            Chain::OpBNB => 500,
-            Chain::Mantle => 501,
-            Chain::Manta => 502,
-            Chain::Scroll => 503,
-            Chain::Blast => 504,
-            Chain::Mode => 505,
-            Chain::Rootstock => 506,
-            Chain::Sei => 507,
-            Chain::Gravity => 508,
-            Chain::Apechain => 509,
-            Chain::Sonic => 510,
-            Chain::Berachain => 511,
-            _ => 0,
+        chain.chain_id().map_or(0, |id| id as i32)
+    }
+}
+
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    /// Accepts the same strings as [`FromStr`], as well as numeric chain ids.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChainVisitor;
+
+        impl de::Visitor<'_> for ChainVisitor {
+            type Value = Chain;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a chain code or chain id")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Chain, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Chain, E> {
+                Ok(Chain::from_chain_id(v).unwrap_or_else(|| Chain::Unknown(v.to_string())))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Chain, E> {
+                u64::try_from(v)
+                    .map_err(|_| E::custom(format!("invalid chain id: {v}")))
+                    .and_then(|v| self.visit_u64(v))
+            }
         }
+
+        deserializer.deserialize_any(ChainVisitor)
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_display_and_parsing_agree() {
+        for chain in Chain::ALL {
+            let code = chain.to_string();
+            assert_eq!(serde_json::to_string(&chain).unwrap(), format!("\"{code}\""));
+            assert_eq!(serde_json::from_str::<Chain>(&format!("\"{code}\"")).unwrap(), chain);
+            assert_eq!(code.parse::<Chain>().unwrap(), chain);
+            assert_eq!(Chain::try_from(format!("{chain:?}")).unwrap(), chain);
+            if let Some(id) = chain.chain_id() {
+                assert_eq!(id.to_string().parse::<Chain>().unwrap(), chain);
+                assert_eq!(serde_json::from_str::<Chain>(&id.to_string()).unwrap(), chain);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_chains() {
+        let chain: Chain = "newchain".parse().unwrap();
+        assert_eq!(chain, Chain::Unknown("newchain".to_string()));
+        assert_eq!(chain.to_string(), "newchain");
+        assert_eq!(serde_json::to_string(&chain).unwrap(), r#""newchain""#);
+        assert!(chain.info().is_none());
+
+        let by_id: Chain = serde_json::from_str("777777").unwrap();
+        assert_eq!(by_id.chain_id(), Some(777777));
+        assert_eq!(format!("/v4/{by_id}/quote"), "/v4/777777/quote");
+
+        assert!("".parse::<Chain>().is_err());
+        assert!("bad/chain".parse::<Chain>().is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::Chain;
+
+// https://apis.openocean.finance/developer/apis/supported-chains
+// https://apis.openocean.finance/developer/contracts-of-chains
+
+
+/// Address OpenOcean uses for the native token on EVM chains.
+pub const EVM_NATIVE_ADDRESS: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// OpenOcean exchange proxy, deployed at the same address on most EVM chains.
+pub const OPENOCEAN_EXCHANGE_V2: &str = "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChainFamily {
+    Evm,
+    Solana,
+    Near,
+    Starknet,
+    Ontology,
+}
+
+/// Static metadata about a [`Chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainInfo {
+    pub chain: Chain,
+    /// Code used in OpenOcean API paths, e.g. `bsc` in `/v4/bsc/quote`.
+    pub code: &'static str,
+    pub name: &'static str,
+    /// EIP-155 chain id, `None` for non-EVM chains.
+    pub chain_id: Option<u64>,
+    pub family: ChainFamily,
+    pub native_symbol: &'static str,
+    pub native_decimals: u8,
+    /// Address OpenOcean uses for the native token in quotes and swaps.
+    pub native_address: Option<&'static str>,
+    pub wrapped_native: Option<&'static str>,
+    pub explorer_url: &'static str,
+    /// OpenOcean exchange contract, the `to` of swap transactions.
+    pub exchange_contract: Option<&'static str>,
+}
+
+impl ChainInfo {
+    pub fn is_evm(&self) -> bool {
+        self.family == ChainFamily::Evm
+    }
+
+    pub fn tx_url(&self, hash: &str) -> String {
+        let path = match self.family {
+            ChainFamily::Near => "txns",
+            ChainFamily::Ontology => "transaction",
+            _ => "tx",
+        };
+        format!("{}/{}/{}", self.explorer_url, path, hash)
+    }
+
+    pub fn address_url(&self, address: &str) -> String {
+        let path = match self.family {
+            ChainFamily::Solana => "account",
+            ChainFamily::Starknet => "contract",
+            _ => "address",
+        };
+        format!("{}/{}/{}", self.explorer_url, path, address)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn evm(
+    chain: Chain,
+    code: &'static str,
+    name: &'static str,
+    chain_id: u64,
+    native_symbol: &'static str,
+    wrapped_native: &'static str,
+    explorer_url: &'static str,
+    exchange_contract: &'static str,
+) -> ChainInfo {
+    ChainInfo {
+        chain,
+        code,
+        name,
+        chain_id: Some(chain_id),
+        family: ChainFamily::Evm,
+        native_symbol,
+        native_decimals: 18,
+        native_address: Some(EVM_NATIVE_ADDRESS),
+        wrapped_native: Some(wrapped_native),
+        explorer_url,
+        exchange_contract: Some(exchange_contract),
+    }
+}
+
+const X: &str = OPENOCEAN_EXCHANGE_V2;
+
+static CHAINS: [ChainInfo; Chain::ALL.len()] = [
+    evm(Chain::Eth, "eth", "Ethereum", 1, "ETH", "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", "https://etherscan.io", X),
+    evm(Chain::Bsc, "bsc", "BNB Chain", 56, "BNB", "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c", "https://bscscan.com", X),
+    evm(Chain::ZkSyncEra, "zksync", "zkSync Era", 324, "ETH", "0x5AEa5775959fBC2557Cc8789bC1bf90A239D9a91", "https://explorer.zksync.io", "0x36A1aCbbCAfca2468b85011DDD16E7Cb4d673230"),
+    evm(Chain::Polygon, "polygon", "Polygon", 137, "POL", "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270", "https://polygonscan.com", X),
+    evm(Chain::Base, "base", "Base", 8453, "ETH", "0x4200000000000000000000000000000000000006", "https://basescan.org", X),
+    evm(Chain::Linea, "linea", "Linea", 59144, "ETH", "0xe5D7C2a44FfDDf6b295A15c148167daaAf5Cf34f", "https://lineascan.build", X),
+    evm(Chain::Fantom, "fantom", "Fantom", 250, "FTM", "0x21be370D5312f44cB42ce377BC9b8a0cEF1A4C83", "https://ftmscan.com", X),
+    evm(Chain::Avalanche, "avax", "Avalanche C-Chain", 43114, "AVAX", "0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7", "https://snowtrace.io", X),
+    evm(Chain::Arbitrum, "arbitrum", "Arbitrum One", 42161, "ETH", "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1", "https://arbiscan.io", X),
+    evm(Chain::Optimism, "optimism", "OP Mainnet", 10, "ETH", "0x4200000000000000000000000000000000000006", "https://optimistic.etherscan.io", X),
+    evm(Chain::Moonriver, "moonriver", "Moonriver", 1285, "MOVR", "0x98878B06940aE243284CA214f92Bb71a2b032B8A", "https://moonriver.moonscan.io", X),
+    evm(Chain::Aurora, "aurora", "Aurora", 1313161554, "ETH", "0xC9BdeEd33CD01541e1eeD10f90519d2C06Fe3feB", "https://explorer.aurora.dev", X),
+    evm(Chain::Cronos, "cronos", "Cronos", 25, "CRO", "0x5C7F8A570d578ED84E63fdFA7b1eE72dEae1AE23", "https://cronoscan.com", X),
+    evm(Chain::Harmony, "harmony", "Harmony", 1666600000, "ONE", "0xcF664087a5bB0237a0BAd6742852ec6c8d69A27a", "https://explorer.harmony.one", X),
+    evm(Chain::Kava, "kava", "Kava EVM", 2222, "KAVA", "0xc86c7C0eFbd6A49B35E8714C5f59D99De09A225b", "https://kavascan.com", X),
+    evm(Chain::MetisAndromeda, "metis", "Metis Andromeda", 1088, "METIS", "0x75cb093E4D61d2A2e65D8e0BBb01DE8d89b53481", "https://andromeda-explorer.metis.io", X),
+    evm(Chain::Celo, "celo", "Celo", 42220, "CELO", "0x471EcE3750Da237f93B8E339c536989b8978a438", "https://celoscan.io", X),
+    evm(Chain::Telos, "telos", "Telos EVM", 40, "TLOS", "0xD102cE6A4dB07D247fcc28F366A623Df0938CA9E", "https://teloscan.io", X),
+    evm(Chain::PolygonZkEVM, "polygon_zkevm", "Polygon zkEVM", 1101, "ETH", "0x4F9A0e7FD2Bf6067db6994CF12E4495Df938E6e9", "https://zkevm.polygonscan.com", X),
+    evm(Chain::Gnosis, "gnosis", "Gnosis", 100, "XDAI", "0xe91D153E0b41518A2Ce8Dd3D7944Fa863463a97d", "https://gnosisscan.io", X),
+    evm(Chain::OpBNB, "opbnb", "opBNB", 204, "BNB", "0x4200000000000000000000000000000000000006", "https://opbnb.bscscan.com", X),
+    evm(Chain::Mantle, "mantle", "Mantle", 5000, "MNT", "0x78c1b0C915c4FAA5FffA6CAbf0219DA63d7f4cb8", "https://mantlescan.xyz", X),
+    evm(Chain::Manta, "manta", "Manta Pacific", 169, "ETH", "0x0Dc808adcE2099A9F62AA87D9670745AbA741746", "https://pacific-explorer.manta.network", X),
+    evm(Chain::Scroll, "scroll", "Scroll", 534352, "ETH", "0x5300000000000000000000000000000000000004", "https://scrollscan.com", X),
+    evm(Chain::Blast, "blast", "Blast", 81457, "ETH", "0x4300000000000000000000000000000000000004", "https://blastscan.io", X),
+    evm(Chain::Mode, "mode", "Mode", 34443, "ETH", "0x4200000000000000000000000000000000000006", "https://explorer.mode.network", X),
+    evm(Chain::Rootstock, "rootstock", "Rootstock", 30, "RBTC", "0x542fDA317318eBF1d3DEAf76E0b632741A7e677d", "https://explorer.rootstock.io", X),
+    evm(Chain::Sei, "sei", "Sei EVM", 1329, "SEI", "0xE30feDd158A2e3b13e9badaeABaFc5516e95e8C7", "https://seitrace.com", X),
+    evm(Chain::Gravity, "gravity", "Gravity Alpha", 1625, "G", "0xBB859E225ac8Fb6BE1C7e38D87b767e95Fef0EbD", "https://explorer.gravity.xyz", X),
+    evm(Chain::Apechain, "ape", "ApeChain", 33139, "APE", "0x48b62137EdfA95a428D35C09E44256a739F6B557", "https://apescan.io", X),
+    evm(Chain::Sonic, "sonic", "Sonic", 146, "S", "0x039e2fB66102314Ce7b64Ce5Ce3E5183bc94aD38", "https://sonicscan.org", X),
+    evm(Chain::Berachain, "bera", "Berachain", 80094, "BERA", "0x6969696969696969696969696969696969696969", "https://berascan.com", X),
+    evm(Chain::MonadTestnet, "monad", "Monad Testnet", 10143, "MON", "0x760AfE86e5de5fa0Ee542fc7B7B713e1c5425701", "https://testnet.monadexplorer.com", X),
+    evm(Chain::UniChain, "uni", "Unichain", 130, "ETH", "0x4200000000000000000000000000000000000006", "https://uniscan.xyz", X),
+    evm(Chain::Flare, "flare", "Flare", 14, "FLR", "0x1D80c49BbBCd1C0911346656B529DF9E5c2F783d", "https://flare-explorer.flare.network", X),
+    evm(Chain::Swell, "swell", "Swellchain", 1923, "ETH", "0x4200000000000000000000000000000000000006", "https://explorer.swellnetwork.io", X),
+    evm(Chain::HyperEVM, "hyperevm", "HyperEVM", 999, "HYPE", "0x5555555555555555555555555555555555555555", "https://hyperevmscan.io", X),
+    evm(Chain::Plume, "plume", "Plume", 98866, "PLUME", "0xEa237441c92CAe6FC17Caaf9a7acB3f953be4bd1", "https://explorer.plume.org", X),
+    ChainInfo {
+        chain: Chain::TAC,
+        code: "tac",
+        name: "TAC",
+        chain_id: Some(239),
+        family: ChainFamily::Evm,
+        native_symbol: "TAC",
+        native_decimals: 18,
+        native_address: Some(EVM_NATIVE_ADDRESS),
+        wrapped_native: None,
+        explorer_url: "https://explorer.tac.build",
+        exchange_contract: Some(X),
+    },
+    ChainInfo {
+        chain: Chain::Solana,
+        code: "solana",
+        name: "Solana",
+        chain_id: None,
+        family: ChainFamily::Solana,
+        native_symbol: "SOL",
+        native_decimals: 9,
+        native_address: Some("So11111111111111111111111111111111111111112"),
+        wrapped_native: Some("So11111111111111111111111111111111111111112"),
+        explorer_url: "https://solscan.io",
+        exchange_contract: None,
+    },
+    ChainInfo {
+        chain: Chain::Ontology,
+        code: "ont",
+        name: "Ontology",
+        chain_id: None,
+        family: ChainFamily::Ontology,
+        native_symbol: "ONT",
+        native_decimals: 9,
+        native_address: None,
+        wrapped_native: None,
+        explorer_url: "https://explorer.ont.io",
+        exchange_contract: None,
+    },
+    ChainInfo {
+        chain: Chain::Near,
+        code: "near",
+        name: "NEAR",
+        chain_id: None,
+        family: ChainFamily::Near,
+        native_symbol: "NEAR",
+        native_decimals: 24,
+        native_address: None,
+        wrapped_native: Some("wrap.near"),
+        explorer_url: "https://nearblocks.io",
+        exchange_contract: None,
+    },
+    ChainInfo {
+        chain: Chain::Starknet,
+        code: "starknet",
+        name: "Starknet",
+        chain_id: None,
+        family: ChainFamily::Starknet,
+        native_symbol: "ETH",
+        native_decimals: 18,
+        native_address: Some("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+        wrapped_native: None,
+        explorer_url: "https://starkscan.co",
+        exchange_contract: None,
+    },
+];
+
+impl Chain {
+    /// Metadata for this chain, `None` for [`Chain::Unknown`].
+    pub fn info(&self) -> Option<&'static ChainInfo> {
+        CHAINS.iter().find(|info| info.chain == *self)
+    }
+
+    /// Code used in OpenOcean API paths.
+    pub fn code(&self) -> &str {
+        match self {
+            Chain::Unknown(code) => code,
+            chain => chain.info().map_or("", |info| info.code),
+        }
+    }
+
+    /// The EVM chain id; for unknown chains, the id they were parsed from.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Chain::Unknown(code) => code.parse().ok(),
+            chain => chain.info().and_then(|info| info.chain_id),
+        }
+    }
+
+    pub fn family(&self) -> Option<ChainFamily> {
+        self.info().map(|info| info.family)
+    }
+
+    /// Looks up the chain with the EIP-155 `chain_id`.
+    pub fn from_chain_id(chain_id: u64) -> Option<Chain> {
+        CHAINS
+            .iter()
+            .find(|info| info.chain_id == Some(chain_id))
+            .map(|info| info.chain.clone())
+    }
+
+    /// Looks up the chain by its OpenOcean API code.
+    pub fn from_code(code: &str) -> Option<Chain> {
+        CHAINS.iter().find(|info| info.code == code).map(|info| info.chain.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_registry_covers_every_chain() {
+        let mut ids = HashSet::new();
+        let mut codes = HashSet::new();
+
+        for chain in Chain::ALL {
+            let info = chain.info().unwrap();
+            assert_eq!(info.chain, chain);
+            assert!(codes.insert(info.code));
+            assert_eq!(Chain::from_code(info.code).as_ref(), Some(&chain));
+
+            match info.chain_id {
+                Some(id) => {
+                    assert!(ids.insert(id), "duplicate chain id {id}");
+                    assert_eq!(Chain::from_chain_id(id), Some(chain));
+                    assert!(info.is_evm());
+                }
+                None => assert!(!info.is_evm()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_chain_ids() {
+        assert_eq!(Chain::Cronos.chain_id(), Some(25));
+        assert_eq!(Chain::Linea.chain_id(), Some(59144));
+        assert_eq!(Chain::from_chain_id(8453), Some(Chain::Base));
+        assert_eq!(Chain::from_chain_id(12345), None);
+        assert_eq!(i32::from(Chain::OpBNB), 204);
+        assert_eq!(i32::from(Chain::Solana), 0);
+
+        assert_eq!(
+            Chain::Solana.info().unwrap().address_url("So11111111111111111111111111111111111111112"),
+            "https://solscan.io/account/So11111111111111111111111111111111111111112"
+        );
+    }
+}
@@ -1,8 +1,10 @@
+use std::fmt;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use crate::OpenoceanError;
-use reqwest::{Client, Url};
-use reqwest::header::CONTENT_TYPE;
+use crate::{api::chains::{builtin_chains, ChainEntry}, cache::{Lookup, ResponseCache}, models::base::{api_error, is_success_code, EnvelopeStatus}, rate_limit::parse_retry_after, single_flight::SingleFlight, telemetry::{self, RequestSpan}, auth::RedactedHeaders, ApiKey, CacheConfig, Capabilities, Chain, Feature, HttpRequest, HttpResponse, OpenoceanError, Quota, RateLimitConfig, RateLimitMode, RateLimiter, ReqwestTransport, RetryPolicy, Transport};
+use reqwest::{Method, Url};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER, USER_AGENT};
 
 
 // https://apis.openocean.finance/developer/widget/widget-v2
@@ -22,27 +24,74 @@ fn body_excerpt(bytes: &[u8]) -> String {
 
 
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct OpenoceanConfig {
     pub base_url: Url,
     pub timeout: Duration,
     pub user_agent: Option<String>,
+    pub retry: RetryPolicy,
+    pub rate_limit: RateLimitConfig,
+    pub cache: CacheConfig,
+    /// Share one in-flight request between concurrent identical GETs.
+    pub coalesce_requests: bool,
+    /// Overrides of the per-chain feature table checked before each call.
+    pub capabilities: Capabilities,
+    /// Sent on every request in the `api_key_header` header.
+    pub api_key: Option<ApiKey>,
+    pub api_key_header: String,
+    /// Extra headers sent on every request.
+    pub default_headers: HeaderMap,
+}
+
+// 手写 Debug：default_headers 里可能带着 API key，不能原样打印
+impl fmt::Debug for OpenoceanConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenoceanConfig")
+            .field("base_url", &self.base_url.as_str())
+            .field("timeout", &self.timeout)
+            .field("user_agent", &self.user_agent)
+            .field("retry", &self.retry)
+            .field("rate_limit", &self.rate_limit)
+            .field("cache", &self.cache)
+            .field("coalesce_requests", &self.coalesce_requests)
+            .field("capabilities", &self.capabilities)
+            .field("api_key", &self.api_key)
+            .field("api_key_header", &self.api_key_header)
+            .field("default_headers", &RedactedHeaders { headers: &self.default_headers, api_key_header: &self.api_key_header })
+            .finish()
+    }
 }
 
 impl Default for OpenoceanConfig {
     fn default() -> Self {
         Self {
-            base_url: Url::parse("https://open-api.openocean.finance").unwrap(),
+            base_url: Url::parse(Self::PUBLIC_BASE_URL).unwrap(),
             timeout: Duration::from_secs(30),
             user_agent: Some(format!("openocean-rs/{}", env!("CARGO_PKG_VERSION"))),
+            retry: RetryPolicy::default(),
+            rate_limit: RateLimitConfig::default(),
+            cache: CacheConfig::default(),
+            coalesce_requests: true,
+            capabilities: Capabilities::default(),
+            api_key: None,
+            api_key_header: crate::DEFAULT_API_KEY_HEADER.to_string(),
+            default_headers: HeaderMap::new(),
         }
     }
 }
 
 impl OpenoceanConfig {
+    pub const PUBLIC_BASE_URL: &'static str = "https://open-api.openocean.finance";
+    pub const PRO_BASE_URL: &'static str = "https://open-api-pro.openocean.finance";
+
     pub fn builder() -> OpenoceanConfigBuilder {
         OpenoceanConfigBuilder::default()
     }
+
+    /// Config for the OpenOcean Pro API using `api_key`.
+    pub fn pro(api_key: impl Into<ApiKey>) -> Self {
+        Self::builder().pro().api_key(api_key).build()
+    }
 }
 
 #[derive(Default)]
@@ -50,6 +99,14 @@ pub struct OpenoceanConfigBuilder {
     base_url: Option<Url>,
     timeout: Option<Duration>,
     user_agent: Option<String>,
+    retry: Option<RetryPolicy>,
+    rate_limit: RateLimitConfig,
+    cache: CacheConfig,
+    coalesce_requests: Option<bool>,
+    capabilities: Capabilities,
+    api_key: Option<ApiKey>,
+    api_key_header: Option<String>,
+    default_headers: HeaderMap,
 }
 
 impl OpenoceanConfigBuilder {
@@ -58,6 +115,30 @@ impl OpenoceanConfigBuilder {
         self
     }
 
+    /// Uses the OpenOcean Pro API base url.
+    pub fn pro(self) -> Self {
+        self.base_url(OpenoceanConfig::PRO_BASE_URL)
+    }
+
+    pub fn api_key(mut self, key: impl Into<ApiKey>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Overrides the header carrying the API key (defaults to `apikey`).
+    pub fn api_key_header(mut self, name: impl Into<String>) -> Self {
+        self.api_key_header = Some(name.into());
+        self
+    }
+
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers.insert(
+            HeaderName::from_bytes(name.as_bytes()).expect("valid header name"),
+            HeaderValue::from_str(value).expect("valid header value"),
+        );
+        self
+    }
+
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
@@ -68,62 +149,230 @@ impl OpenoceanConfigBuilder {
         self
     }
 
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Caps the total request rate of the client.
+    pub fn rate_limit(mut self, quota: Quota) -> Self {
+        self.rate_limit.global = Some(quota);
+        self
+    }
+
+    /// Caps the request rate of paths matching `pattern`, e.g. `/v4/{chain}/quote`.
+    pub fn endpoint_rate_limit(mut self, pattern: impl Into<String>, quota: Quota) -> Self {
+        self.rate_limit.endpoints.push((pattern.into(), quota));
+        self
+    }
+
+    pub fn rate_limit_mode(mut self, mode: RateLimitMode) -> Self {
+        self.rate_limit.mode = mode;
+        self
+    }
+
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Caches successful GET responses of paths matching `pattern` for `ttl`.
+    pub fn cache_ttl(mut self, pattern: impl Into<String>, ttl: Duration) -> Self {
+        self.cache.ttls.push((pattern.into(), ttl));
+        self
+    }
+
+    pub fn cache_max_entries(mut self, max_entries: usize) -> Self {
+        self.cache.max_entries = max_entries;
+        self
+    }
+
+    /// Serves expired cache entries for up to `window` while refreshing them in the background.
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.cache.stale_while_revalidate = window;
+        self
+    }
+
+    /// Whether concurrent identical GETs share one request (enabled by default).
+    pub fn coalesce_requests(mut self, enabled: bool) -> Self {
+        self.coalesce_requests = Some(enabled);
+        self
+    }
+
+    /// Overrides whether `chain` supports `feature`, see [`Chain::supports`].
+    pub fn capability(mut self, chain: Chain, feature: Feature, enabled: bool) -> Self {
+        self.capabilities.set(chain, feature, enabled);
+        self
+    }
+
     pub fn build(self) -> OpenoceanConfig {
         OpenoceanConfig {
-            base_url: self.base_url.unwrap_or_else(|| Url::parse("https://open-api.openocean.finance").unwrap()),
+            base_url: self.base_url.unwrap_or_else(|| Url::parse(OpenoceanConfig::PUBLIC_BASE_URL).unwrap()),
             timeout: self.timeout.unwrap_or(Duration::from_secs(30)),
             user_agent: self.user_agent.or_else(|| Some(format!("openocean-rs/{}", env!("CARGO_PKG_VERSION")))),
+            retry: self.retry.unwrap_or_default(),
+            rate_limit: self.rate_limit,
+            cache: self.cache,
+            coalesce_requests: self.coalesce_requests.unwrap_or(true),
+            capabilities: self.capabilities,
+            api_key: self.api_key,
+            api_key_header: self.api_key_header.unwrap_or_else(|| crate::DEFAULT_API_KEY_HEADER.to_string()),
+            default_headers: self.default_headers,
         }
     }
 }
 
+/// OpenOcean API client.
+///
+/// Cloning is cheap: clones share the transport (and its connection pool), the
+/// rate limiter and the configuration, so a client can be handed to many
+/// spawned tasks.
+#[derive(Clone, Debug)]
 pub struct OpenoceanClient {
+    inner: Arc<ClientInner>,
+}
+
+#[derive(Debug)]
+struct ClientInner {
     config: OpenoceanConfig,
-    client: Client,
+    transport: Arc<dyn Transport>,
+    rate_limiter: RateLimiter,
+    cache: ResponseCache,
+    in_flight: SingleFlight,
+    capabilities: RwLock<Capabilities>,
+    chains: RwLock<Vec<ChainEntry>>,
+    headers: HeaderMap,
 }
 
 impl OpenoceanClient {
     pub fn new(config: OpenoceanConfig) -> Result<Self, OpenoceanError> {
-        let mut builder = Client::builder().timeout(config.timeout);
+        let transport = ReqwestTransport::new(&config)?;
+        Self::with_transport(config, transport)
+    }
 
+    /// Creates a client sending its requests through `transport`.
+    pub fn with_transport(config: OpenoceanConfig, transport: impl Transport + 'static) -> Result<Self, OpenoceanError> {
+        let rate_limiter = RateLimiter::new(&config.rate_limit)?;
+        let cache = ResponseCache::new(&config.cache);
+        let capabilities = RwLock::new(config.capabilities.clone());
+
+        let mut headers = config.default_headers.clone();
         if let Some(ua) = &config.user_agent {
-            builder = builder.user_agent(ua.clone());
+            let value = HeaderValue::from_str(ua)
+                .map_err(|e| OpenoceanError::Internal(format!("invalid user agent: {e}")))?;
+            headers.insert(USER_AGENT, value);
+        }
+        if let Some(key) = &config.api_key {
+            let name = HeaderName::from_bytes(config.api_key_header.as_bytes())
+                .map_err(|e| OpenoceanError::Internal(format!("invalid api key header name: {e}")))?;
+            let mut value = HeaderValue::from_str(key.expose())
+                .map_err(|_| OpenoceanError::Internal("invalid api key header value".into()))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
         }
 
-        let client = builder
-            .build()
-            .map_err(|e| OpenoceanError::Network(format!("failed to build http client: {e}")))?;
+        Ok(Self {
+            inner: Arc::new(ClientInner {
+                config,
+                transport: Arc::new(transport),
+                rate_limiter,
+                cache,
+                in_flight: SingleFlight::default(),
+                capabilities,
+                chains: RwLock::new(builtin_chains()),
+                headers,
+            }),
+        })
+    }
 
-        Ok(Self { config, client })
+    pub fn config(&self) -> &OpenoceanConfig {
+        &self.inner.config
+    }
+
+    /// Drops cached responses of paths matching `pattern`, e.g. `/v4/bsc/tokenList`
+    /// or `/v4/{chain}/tokenList`.
+    pub fn invalidate_cache(&self, pattern: &str) {
+        self.inner.cache.invalidate(pattern);
+    }
+
+    pub fn clear_cache(&self) {
+        self.inner.cache.clear();
+    }
+
+    /// Whether `feature` may be called on `chain`, taking overrides into account.
+    pub fn supports(&self, chain: &Chain, feature: Feature) -> bool {
+        self.inner.capabilities.read().unwrap().supports(chain, feature)
+    }
+
+    /// Overrides the capability table for this client and all its clones.
+    pub fn set_capability(&self, chain: Chain, feature: Feature, enabled: bool) {
+        self.inner.capabilities.write().unwrap().set(chain, feature, enabled);
+    }
+
+    pub(crate) fn chain_entries(&self) -> &RwLock<Vec<ChainEntry>> {
+        &self.inner.chains
+    }
+
+    /// Fails with [`OpenoceanError::UnsupportedChain`] if `feature` is not available on `chain`.
+    pub(crate) fn ensure_supported(&self, chain: &Chain, feature: Feature) -> Result<(), OpenoceanError> {
+        self.inner.capabilities.read().unwrap().check(chain, feature)
     }
 
     #[inline]
     fn build_url(&self, path: &str) -> Result<Url, OpenoceanError> {
-        self.config
+        self.inner
+            .config
             .base_url
             .join(path)
             .map_err(|e| OpenoceanError::Internal(format!("join url error: {e}")))
     }
 
-    async fn parse_json<T: DeserializeOwned>(resp: reqwest::Response) -> Result<T, OpenoceanError> {
-        let status = resp.status();
-        let content_type = resp
-            .headers()
-            .get(CONTENT_TYPE)
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
+    /// Builds a request carrying the user agent, default and API key headers.
+    fn request(&self, method: Method, url: Url, body: Option<Vec<u8>>) -> HttpRequest {
+        let mut headers = self.inner.headers.clone();
+        if body.is_some() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
 
-        // 先把原始字节读出来；不论成功失败都有“证据”
-        let bytes = resp.bytes().await.map_err(OpenoceanError::from)?;
+        HttpRequest { method, url, headers, body }
+    }
+
+    /// Body excerpt for error messages, with the API key scrubbed.
+    fn excerpt(&self, bytes: &[u8]) -> String {
+        match &self.inner.config.api_key {
+            // redact before truncating so a key cut in half cannot leak
+            Some(key) => body_excerpt(key.redact(&String::from_utf8_lossy(bytes)).as_bytes()),
+            None => body_excerpt(bytes),
+        }
+    }
+
+    fn parse_json<T: DeserializeOwned>(&self, path: &str, resp: HttpResponse) -> Result<T, OpenoceanError> {
+        // transport 已经把原始字节读出来；不论成功失败都有“证据”
+        if !resp.is_success() {
+            let content_type = resp
+                .headers
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string());
 
-        if !status.is_success() {
             return Err(OpenoceanError::Http {
-                status: status.as_u16(),
-                body: body_excerpt(&bytes),
+                status: resp.status,
+                body: self.excerpt(&resp.body),
                 content_type,
             });
         }
 
+        let bytes = resp.body;
+
+        // 业务失败时 data 往往为 null 或形状不同，优先报告业务错误；
+        // 在这里处理而不是留给 Envelope::check，因为只有这里拿得到 API key 做脱敏
+        if let Ok(status) = serde_json::from_slice::<EnvelopeStatus>(&bytes) {
+            if !is_success_code(status.code) {
+                let message = status.error_msg.unwrap_or_default();
+                return Err(api_error(status.code, &self.excerpt(message.as_bytes()), path));
+            }
+        }
+
         // 使用 serde_path_to_error 捕获精确路径
         let mut de = serde_json::Deserializer::from_slice(&bytes);
         match serde_path_to_error::deserialize::<_, T>(&mut de) {
@@ -134,16 +383,133 @@ impl OpenoceanClient {
                 Err(OpenoceanError::Parse {
                     message,
                     path,
-                    body: body_excerpt(&bytes),
+                    body: self.excerpt(&bytes),
                 })
             }
         }
     }
 
+    /// Sends `request`, retrying according to the configured [`RetryPolicy`].
+    ///
+    /// Every attempt first takes a token from the rate limiter for `path`.
+    /// Non-idempotent requests are only retried if the policy explicitly allows it.
+    async fn send(&self, path: &str, request: HttpRequest, idempotent: bool, span: &RequestSpan) -> Result<HttpResponse, OpenoceanError> {
+        let policy = &self.inner.config.retry;
+        let mut attempt = 1;
+
+        loop {
+            self.inner.rate_limiter.acquire(path).await?;
+            let result = self.inner.transport.send(request.clone()).await;
+
+            if let Ok(resp) = &result {
+                if resp.status == 429 {
+                    telemetry::record_rate_limited(path, "server");
+                    let retry_after = resp
+                        .headers
+                        .get(RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_retry_after);
+                    if let Some(retry_after) = retry_after {
+                        self.inner.rate_limiter.penalize(path, retry_after);
+                    }
+                }
+            }
+
+            let reason = match result {
+                Ok(resp) if policy.should_retry_status(resp.status, attempt, idempotent) => format!("http status {}", resp.status),
+                Ok(resp) => {
+                    span.record_response(&resp, attempt);
+                    return Ok(resp);
+                }
+                Err(err) if policy.should_retry_error(&err, attempt, idempotent) => err.to_string(),
+                Err(err) => return Err(err.into()),
+            };
+
+            let delay = policy.backoff(attempt);
+            telemetry::retrying(attempt, delay, &reason);
+            telemetry::record_retry(path);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Serves `request` from the response cache when its path is cached and
+    /// joins an identical in-flight GET if there is one, sending it otherwise.
+    /// The flag is `true` when the response came from the cache.
+    async fn fetch(&self, path: &str, request: HttpRequest, idempotent: bool, span: &RequestSpan) -> Result<(HttpResponse, bool), OpenoceanError> {
+        if request.method != Method::GET {
+            return Ok((self.send(path, request, idempotent, span).await?, false));
+        }
+
+        let key = request.url.to_string();
+        let ttl = self.inner.cache.ttl(path);
+        if let Some(ttl) = ttl {
+            match self.inner.cache.get(&key) {
+                Lookup::Fresh(resp) => return Ok((resp, true)),
+                Lookup::Stale { response, refresh } => {
+                    if refresh {
+                        self.spawn_refresh(path, key, ttl, request);
+                    }
+                    return Ok((response, true));
+                }
+                Lookup::Miss => {}
+            }
+        }
+
+        let fetch = async {
+            let resp = self.send(path, request, idempotent, span).await?;
+            if let Some(ttl) = ttl {
+                self.inner.cache.store(&key, path, ttl, &resp);
+            }
+            Ok(resp)
+        };
+
+        let resp = if self.inner.config.coalesce_requests {
+            self.inner.in_flight.run(&key, fetch).await?
+        } else {
+            fetch.await?
+        };
+        Ok((resp, false))
+    }
+
+    fn spawn_refresh(&self, path: &str, key: String, ttl: Duration, request: HttpRequest) {
+        let client = self.clone();
+        let path = path.to_string();
+
+        tokio::spawn(async move {
+            let span = RequestSpan::new(&path, &request);
+            let result = span.instrument(client.send(&path, request, true, &span)).await;
+            match &result {
+                Ok(resp) => client.inner.cache.store(&key, &path, ttl, resp),
+                Err(_) => client.inner.cache.refresh_failed(&key),
+            }
+            span.finish(&result);
+        });
+    }
+
+    /// Sends `request` and parses the response, inside a request span when the
+    /// `tracing` feature is enabled and recording metrics with `metrics`.
+    async fn execute<T: DeserializeOwned>(&self, path: &str, request: HttpRequest, idempotent: bool) -> Result<T, OpenoceanError> {
+        let span = RequestSpan::new(path, &request);
+        let method = request.method.clone();
+        let started = tokio::time::Instant::now();
+        let mut cached = false;
+        let result = span
+            .instrument(async {
+                let (resp, hit) = self.fetch(path, request, idempotent, &span).await?;
+                cached = hit;
+                self.parse_json(path, resp)
+            })
+            .await;
+        telemetry::record_request(path, &method, &result, cached, started.elapsed());
+        span.finish(&result);
+        result
+    }
+
     pub(super) async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, OpenoceanError> {
         let url = self.build_url(path)?;
-        let resp = self.client.get(url).send().await?;
-        Self::parse_json(resp).await
+        let req = self.request(Method::GET, url, None);
+        self.execute(path, req, true).await
     }
 
     pub(super) async fn get_json_with_query<T, Q>(&self, path: &str, query: &Q) -> Result<T, OpenoceanError>
@@ -151,9 +517,15 @@ impl OpenoceanClient {
         T: DeserializeOwned,
         Q: Serialize,
     {
-        let url = self.build_url(path)?;
-        let resp = self.client.get(url).query(query).send().await?;
-        Self::parse_json(resp).await
+        let mut url = self.build_url(path)?;
+        let query = serde_urlencoded::to_string(query)
+            .map_err(|e| OpenoceanError::InvalidParams(format!("failed to encode query: {e}")))?;
+        if !query.is_empty() {
+            url.set_query(Some(&query));
+        }
+
+        let req = self.request(Method::GET, url, None);
+        self.execute(path, req, true).await
     }
 
     #[allow(dead_code)]
@@ -163,13 +535,209 @@ impl OpenoceanClient {
         body: &B,
     ) -> Result<T, OpenoceanError> {
         let url = self.build_url(path)?;
+        let body = serde_json::to_vec(body)
+            .map_err(|e| OpenoceanError::InvalidParams(format!("failed to encode body: {e}")))?;
+        let req = self.request(Method::POST, url, Some(body));
+        self.execute(path, req, false).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{models::limit_order::CancelLimitOrderParams, ApiErrorKind, Chain, Chains, Dca, Gasless, LimitOrder, MockTransport, SweepSwap, Swap, Ticket, Zap};
+
+    fn mock_client(config: OpenoceanConfig) -> (OpenoceanClient, Arc<MockTransport>) {
+        let transport = Arc::new(MockTransport::new());
+        let client = OpenoceanClient::with_transport(config, transport.clone()).unwrap();
+        (client, transport)
+    }
+
+    fn dex_list() -> serde_json::Value {
+        json!({ "code": 200, "data": [{ "index": 1, "code": "PancakeV2", "name": "PancakeSwap" }] })
+    }
+
+    fn assert_spawnable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+    #[test]
+    fn test_client_and_handles_are_spawnable() {
+        let client = OpenoceanClient::new(OpenoceanConfig::default()).unwrap();
+        assert_spawnable(&client);
+        assert_spawnable::<Swap>(&client.swap());
+        assert_spawnable::<Gasless>(&client.gasless());
+        assert_spawnable::<Dca>(&client.dca());
+        assert_spawnable::<LimitOrder>(&client.limit_order());
+        assert_spawnable::<Zap>(&client.zap());
+        assert_spawnable::<SweepSwap>(&client.sweep_swap());
+        assert_spawnable::<Ticket>(&client.ticket());
+        assert_spawnable::<Chains>(&client.chains());
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_transport_with_headers() {
+        let (client, transport) = mock_client(OpenoceanConfig::builder().api_key("secret").user_agent("test/1.0").build());
+        transport.on_json(Method::GET, "/v4/bsc/dexList", dex_list());
 
-        let resp = self.client.post(url)
-            .json(body)
-            .send()
-            .await
-            .map_err(OpenoceanError::from)?;
+        let dexes = client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        assert_eq!(dexes[0].code, "PancakeV2");
 
-        Self::parse_json(resp).await
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url.as_str(), "https://open-api.openocean.finance/v4/bsc/dexList");
+        assert_eq!(requests[0].headers["apikey"], "secret");
+        assert_eq!(requests[0].headers[USER_AGENT], "test/1.0");
     }
-}
\ No newline at end of file
+
+    #[tokio::test(start_paused = true)]
+    async fn test_get_is_retried_on_server_error() {
+        let (client, transport) = mock_client(OpenoceanConfig::default());
+        transport
+            .on(Method::GET, "/v4/bsc/dexList", HttpResponse::new(503, "busy"))
+            .on_json(Method::GET, "/v4/bsc/dexList", dex_list());
+
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_post_is_not_retried() {
+        let (client, transport) = mock_client(OpenoceanConfig::default());
+        transport
+            .on(Method::POST, "/v2/bsc/limit-order/cancelLimitOrder", HttpResponse::new(503, "busy"))
+            .on_json(Method::POST, "/v2/bsc/limit-order/cancelLimitOrder", json!({ "code": 200 }));
+
+        let params = CancelLimitOrderParams::builder()
+            .order_hash("0x01")
+            .signature("0x02")
+            .build()
+            .unwrap();
+        let err = client.limit_order().cancel_limit_order(Chain::Bsc, &params).await.unwrap_err();
+
+        assert!(matches!(err, OpenoceanError::Http { status: 503, .. }));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_envelope_error_becomes_api_error() {
+        let (client, transport) = mock_client(OpenoceanConfig::default());
+        transport.on_json(Method::GET, "/v4/bsc/gasPrice", json!({ "code": 400, "error": "chain not supported" }));
+
+        let err = client.swap().get_price(Chain::Bsc).await.unwrap_err();
+        match err {
+            OpenoceanError::Api { code, kind, endpoint, .. } => {
+                assert_eq!(code, 400);
+                assert_eq!(kind, ApiErrorKind::InvalidParams);
+                assert_eq!(endpoint, "/v4/bsc/gasPrice");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_config_debug_redacts_headers() {
+        let config = OpenoceanConfig::builder()
+            .api_key("secret-key-1")
+            .default_header("apikey", "secret-key-2")
+            .default_header("x-request-source", "sdk")
+            .build();
+
+        let shown = format!("{config:?}");
+        assert!(!shown.contains("secret-key"), "{shown}");
+        assert!(shown.contains("x-request-source"), "{shown}");
+    }
+
+    #[tokio::test]
+    async fn test_api_error_message_is_redacted() {
+        let (client, transport) = mock_client(OpenoceanConfig::builder().api_key("secret-key-123").build());
+        transport.on_json(
+            Method::GET,
+            "/v4/bsc/dexList",
+            json!({ "code": 401, "data": [], "errorMsg": "invalid apikey secret-key-123" }),
+        );
+
+        let err = client.swap().get_dex_list(Chain::Bsc).await.unwrap_err();
+        match err {
+            OpenoceanError::Api { code, message, .. } => {
+                assert_eq!(code, 401);
+                assert_eq!(message, "invalid apikey ***");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cached_responses_are_refreshed_in_background() {
+        let config = OpenoceanConfig::builder()
+            .cache_ttl("/v4/{chain}/dexList", Duration::from_secs(60))
+            .stale_while_revalidate(Duration::from_secs(10))
+            .build();
+        let (client, transport) = mock_client(config);
+        transport.on_json(Method::GET, "/v4/bsc/dexList", dex_list());
+
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        assert_eq!(transport.requests().len(), 1);
+
+        // stale: served from cache while one refresh goes out
+        tokio::time::advance(Duration::from_secs(65)).await;
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(transport.requests().len(), 2);
+
+        client.invalidate_cache("/v4/{chain}/dexList");
+        client.swap().get_dex_list(Chain::Bsc).await.unwrap();
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_identical_concurrent_gets_are_coalesced() {
+        let transport = Arc::new(MockTransport::new().with_latency(Duration::from_millis(50)));
+        let client = OpenoceanClient::with_transport(OpenoceanConfig::default(), transport.clone()).unwrap();
+        transport.on_json(Method::GET, "/v4/bsc/dexList", dex_list());
+
+        let swap = client.swap();
+        let (a, b) = tokio::join!(swap.get_dex_list(Chain::Bsc), swap.get_dex_list(Chain::Bsc));
+        assert_eq!(a.unwrap().len(), b.unwrap().len());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_chain_is_rejected_before_sending() {
+        let (client, transport) = mock_client(OpenoceanConfig::default());
+        let params = CancelLimitOrderParams::builder()
+            .order_hash("0x01")
+            .signature("0x02")
+            .build()
+            .unwrap();
+
+        let err = client.limit_order().cancel_limit_order(Chain::Near, &params).await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::UnsupportedChain { chain: Chain::Near, feature: Feature::LimitOrder }));
+        assert!(transport.requests().is_empty());
+
+        client.set_capability(Chain::Near, Feature::LimitOrder, true);
+        transport.on_json(Method::POST, "/v2/near/limit-order/cancelLimitOrder", json!({ "code": 200 }));
+        client.limit_order().cancel_limit_order(Chain::Near, &params).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_chain_discovery() {
+        let (client, transport) = mock_client(OpenoceanConfig::default());
+        transport.on_json(
+            Method::GET,
+            "/v4/chainList",
+            json!({ "code": 200, "data": [{ "code": "bsc", "chainId": 56 }, { "code": "newchain", "chainId": 999999 }] }),
+        );
+
+        let chains = client.chains();
+        assert_eq!(chains.resolve("newchain"), None);
+
+        chains.refresh().await.unwrap();
+        let resolved = client.clone().chains().resolve("999999").unwrap();
+        assert_eq!(resolved, Chain::Unknown("newchain".into()));
+        assert_eq!(chains.resolve("BSC"), Some(Chain::Bsc));
+    }
+}
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::{Chain, Feature};
+
 
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, Error)]
 pub enum OpenoceanError {
     /// 网络相关错误（reqwest 层面的连接、超时等）
     #[error("network error: {0}")]
@@ -23,11 +27,82 @@ pub enum OpenoceanError {
         body: String,
     },
 
+    /// HTTP 200 但业务返回码表示失败
+    #[error("api error: code={code}, kind={kind:?}, endpoint={endpoint}, message={message}")]
+    Api {
+        code: i32,
+        kind: ApiErrorKind,
+        message: String,
+        endpoint: String,
+    },
+
+    /// 客户端限流拒绝了请求（RateLimitMode::Reject）
+    #[error("rate limited: retry after {retry_after:?}")]
+    RateLimited {
+        retry_after: Duration,
+    },
+
+    /// 请求参数缺失或不合法（由参数 builder 校验）
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+
+    /// 该链不支持所调用的 API（在发出请求前拒绝）
+    #[error("{feature} is not supported on chain {chain}")]
+    UnsupportedChain {
+        chain: Chain,
+        feature: Feature,
+    },
+
+    /// 等待交易回执超时（交易可能仍在 pending）
+    #[error("no receipt for {hash} after {waited:?}")]
+    ReceiptTimeout {
+        hash: String,
+        waited: Duration,
+    },
+
     /// 其它 SDK 内部错误
     #[error("internal error: {0}")]
     Internal(String),
 }
 
+/// Classification of a business-level error reported by the API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    InsufficientLiquidity,
+    UnsupportedToken,
+    InvalidSignature,
+    RateLimited,
+    InvalidParams,
+    NotFound,
+    /// The envelope reported success but carried no data.
+    MissingData,
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Best-effort classification from the envelope code and message.
+    pub fn classify(code: i32, message: &str) -> Self {
+        let msg = message.to_ascii_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| msg.contains(n));
+
+        if code == 429 || has(&["rate limit", "too many request", "frequent"]) {
+            ApiErrorKind::RateLimited
+        } else if has(&["liquidity", "no route", "no available route", "insufficient output"]) {
+            ApiErrorKind::InsufficientLiquidity
+        } else if has(&["signature"]) {
+            ApiErrorKind::InvalidSignature
+        } else if msg.contains("token") && has(&["not support", "unsupported", "not found", "invalid", "not exist"]) {
+            ApiErrorKind::UnsupportedToken
+        } else if code == 404 || has(&["not found", "not exist"]) {
+            ApiErrorKind::NotFound
+        } else if code == 400 || has(&["param", "invalid", "required"]) {
+            ApiErrorKind::InvalidParams
+        } else {
+            ApiErrorKind::Other
+        }
+    }
+}
+
 impl From<reqwest::Error> for OpenoceanError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
@@ -47,4 +122,20 @@ impl From<serde_json::Error> for OpenoceanError {
             body: "".to_string(),
         }
     }
-}*/
\ No newline at end of file
+}*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_api_errors() {
+        assert_eq!(ApiErrorKind::classify(500, "Insufficient liquidity for this trade"), ApiErrorKind::InsufficientLiquidity);
+        assert_eq!(ApiErrorKind::classify(400, "inToken not supported"), ApiErrorKind::UnsupportedToken);
+        assert_eq!(ApiErrorKind::classify(201, "Invalid signature"), ApiErrorKind::InvalidSignature);
+        assert_eq!(ApiErrorKind::classify(429, ""), ApiErrorKind::RateLimited);
+        assert_eq!(ApiErrorKind::classify(400, "amount is required"), ApiErrorKind::InvalidParams);
+        assert_eq!(ApiErrorKind::classify(500, "server busy"), ApiErrorKind::Other);
+    }
+}
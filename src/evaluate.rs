@@ -0,0 +1,209 @@
+use crate::{
+    models::swap::{QuoteData, QuoteToken, ReverseQuoteData, SwapQuoteData},
+    types::{TokenAmount, U256},
+    OpenoceanError,
+};
+
+/// Prices a quote's gas in its output token, so quotes can be compared by
+/// what actually arrives after paying for the transaction.
+///
+/// The gas cost is `estimated_gas * gas_price` in the native token, valued in
+/// USD with `native_usd` and converted back into output-token units with the
+/// quote's `out_token.usd`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteEvaluator {
+    /// Price per gas unit in the native token's smallest unit (wei on EVM chains).
+    pub gas_price: U256,
+    pub native_decimals: u8,
+    /// USD price of one whole native token.
+    pub native_usd: f64,
+}
+
+/// A quote's output with its gas cost taken out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteEvaluation {
+    pub out_amount: TokenAmount,
+    /// Gas cost in the native token.
+    pub gas_cost: TokenAmount,
+    pub gas_cost_usd: f64,
+    /// Gas cost in output-token units.
+    pub gas_cost_out: TokenAmount,
+    /// `out_amount - gas_cost_out`, floored at zero.
+    pub net_out: TokenAmount,
+}
+
+/// The winner of [`QuoteEvaluator::best`] or [`Swap::best_quote`](crate::Swap::best_quote).
+#[derive(Debug)]
+pub struct BestQuote<Q> {
+    /// Position of the winning quote among the candidates.
+    pub index: usize,
+    pub quote: Q,
+    pub evaluation: QuoteEvaluation,
+}
+
+/// Quote responses that carry an output amount and a gas estimate.
+pub trait GasQuote {
+    fn out_token(&self) -> &QuoteToken;
+    fn out_amount(&self) -> U256;
+    fn estimated_gas(&self) -> U256;
+}
+
+macro_rules! impl_gas_quote {
+    ($($ty:ty),*) => {$(
+        impl GasQuote for $ty {
+            fn out_token(&self) -> &QuoteToken {
+                &self.out_token
+            }
+
+            fn out_amount(&self) -> U256 {
+                self.out_amount
+            }
+
+            fn estimated_gas(&self) -> U256 {
+                self.estimated_gas
+            }
+        }
+    )*};
+}
+
+impl_gas_quote!(QuoteData, ReverseQuoteData, SwapQuoteData);
+
+impl QuoteEvaluator {
+    pub fn new(gas_price: impl Into<U256>, native_decimals: u8, native_usd: f64) -> Self {
+        Self { gas_price: gas_price.into(), native_decimals, native_usd }
+    }
+
+    pub fn evaluate(&self, quote: &impl GasQuote) -> Result<QuoteEvaluation, OpenoceanError> {
+        let out_token = quote.out_token();
+        let out_usd = out_token.usd.trim().parse::<f64>().ok().filter(|p| p.is_finite() && *p > 0.0).ok_or_else(|| {
+            OpenoceanError::Internal(format!("quote has no usd price for {}: {:?}", out_token.symbol, out_token.usd))
+        })?;
+        let gas_cost = TokenAmount::new(
+            quote.estimated_gas().checked_mul(self.gas_price).unwrap_or(U256::MAX),
+            self.native_decimals,
+        );
+        let gas_cost_usd = gas_cost.to_f64() * self.native_usd;
+        let gas_cost_out = TokenAmount::new(
+            f64_to_u256(gas_cost_usd / out_usd * 10f64.powi(out_token.decimals as i32)),
+            out_token.decimals,
+        );
+        let out_amount = TokenAmount::new(quote.out_amount(), out_token.decimals);
+
+        Ok(QuoteEvaluation {
+            out_amount,
+            gas_cost,
+            gas_cost_usd,
+            gas_cost_out,
+            net_out: TokenAmount::new(out_amount.raw.saturating_sub(gas_cost_out.raw), out_token.decimals),
+        })
+    }
+
+    /// The quote with the highest net output, keyed by its index.
+    /// Ties go to the earlier quote. Quotes that fail [`evaluate`](Self::evaluate)
+    /// are skipped; the last such error is returned only if no quote could be
+    /// evaluated. `None` if `quotes` is empty.
+    pub fn best<Q: GasQuote>(
+        &self,
+        quotes: impl IntoIterator<Item = (usize, Q)>,
+    ) -> Result<Option<BestQuote<Q>>, OpenoceanError> {
+        let mut best: Option<BestQuote<Q>> = None;
+        let mut last_err = None;
+        for (index, quote) in quotes {
+            let evaluation = match self.evaluate(&quote) {
+                Ok(evaluation) => evaluation,
+                Err(e) => {
+                    last_err = Some(e);
+                    continue;
+                }
+            };
+            let better = match &best {
+                None => true,
+                Some(best) => {
+                    let (net, best_net) = (evaluation.net_out.raw, best.evaluation.net_out.raw);
+                    net > best_net || net == best_net && index < best.index
+                }
+            };
+            if better {
+                best = Some(BestQuote { index, quote, evaluation });
+            }
+        }
+        match (best, last_err) {
+            (None, Some(e)) => Err(e),
+            (best, _) => Ok(best),
+        }
+    }
+}
+
+fn f64_to_u256(v: f64) -> U256 {
+    match v.is_finite() && v >= 1.0 {
+        true => format!("{:e}", v.round()).parse().unwrap_or(U256::MAX),
+        false => U256::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(out_amount: u64, estimated_gas: u64) -> QuoteData {
+        let token = |symbol: &str, usd: &str| {
+            serde_json::json!({
+                "address": "0x55d398326f99059ff775485246999027b3197955",
+                "decimals": 6, "symbol": symbol, "name": symbol, "usd": usd, "volume": 0
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "inToken": token("USDT", "1"),
+            "outToken": token("USDC", "0.5"),
+            "inAmount": "1000000000",
+            "outAmount": out_amount.to_string(),
+            "estimatedGas": estimated_gas.to_string(),
+            "path": { "from": "0x55d398326f99059ff775485246999027b3197955", "to": "0x55d398326f99059ff775485246999027b3197955", "parts": 1, "routes": [] },
+            "save": 0, "price_impact": "0.01%", "exchange": "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gas_in_out_token_units() {
+        // 100k gas at 10 gwei = 0.001 ETH = $3 = 6 USDC at $0.50
+        let evaluator = QuoteEvaluator::new(10_000_000_000u64, 18, 3000.0);
+        let eval = evaluator.evaluate(&quote(1_000_000_000, 100_000)).unwrap();
+
+        assert_eq!(eval.gas_cost.to_units(), "0.001");
+        assert!((eval.gas_cost_usd - 3.0).abs() < 1e-9);
+        assert_eq!(eval.gas_cost_out.raw, U256::from(6_000_000u64));
+        assert_eq!(eval.net_out.to_units(), "994");
+
+        let dust = evaluator.evaluate(&quote(1_000, 100_000)).unwrap();
+        assert!(dust.net_out.raw.is_zero());
+    }
+
+    #[test]
+    fn test_best_by_net_output() {
+        let evaluator = QuoteEvaluator::new(10_000_000_000u64, 18, 3000.0);
+        // More gross output, but 10x the gas (60 USDC) loses to the cheaper route.
+        let quotes = [quote(1_050_000_000, 1_000_000), quote(1_000_000_000, 100_000), quote(1_000_000_000, 100_000)];
+
+        let best = evaluator.best(quotes.into_iter().enumerate()).unwrap().unwrap();
+        assert_eq!(best.index, 1);
+        assert_eq!(best.evaluation.net_out.to_units(), "994");
+
+        assert!(evaluator.best(Vec::<(usize, QuoteData)>::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_best_skips_quotes_that_fail_evaluation() {
+        let evaluator = QuoteEvaluator::new(10_000_000_000u64, 18, 3000.0);
+        let mut bad = quote(2_000_000_000, 100_000);
+        bad.out_token.usd = String::new();
+        let quotes = [quote(1_000_000_000, 1_000_000), bad, quote(1_000_000_000, 100_000)];
+
+        let best = evaluator.best(quotes.into_iter().enumerate()).unwrap().unwrap();
+        assert_eq!(best.index, 2);
+
+        let mut bad = quote(1_000_000_000, 100_000);
+        bad.out_token.usd = String::new();
+        assert!(matches!(evaluator.best([(0, bad)]), Err(OpenoceanError::Internal(_))));
+    }
+}
@@ -2,12 +2,43 @@
 
 mod error;
 mod chain;
-mod models;
+mod chain_info;
+mod capability;
+mod address;
+mod route;
+mod evaluate;
+mod tx;
+mod rpc;
+#[cfg(feature = "signer")]
+mod signer;
+pub mod models;
 mod client;
-mod types;
+mod retry;
+mod rate_limit;
+mod cache;
+mod single_flight;
+mod auth;
+mod transport;
+mod telemetry;
+pub mod types;
 mod api;
 
 pub use error::*;
 pub use chain::*;
+pub use chain_info::*;
+pub use capability::*;
+pub use address::*;
+pub use route::*;
+pub use evaluate::*;
+pub use tx::*;
+pub use rpc::*;
+#[cfg(feature = "signer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signer")))]
+pub use signer::*;
 pub use client::*;
+pub use retry::*;
+pub use rate_limit::*;
+pub use cache::*;
+pub use auth::*;
+pub use transport::*;
 pub use api::*;
\ No newline at end of file
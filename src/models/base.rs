@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{ApiErrorKind, OpenoceanError};
+
 
 
 
@@ -9,4 +11,109 @@ pub struct BaseResponse<T> {
     pub code: i32,
     pub data: Option<T>,
     pub error_msg: Option<String>,
-}
\ No newline at end of file
+}
+
+impl<T> Envelope for BaseResponse<T> {
+    type Data = T;
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        self.error_msg.as_deref()
+    }
+
+    fn into_data(self) -> Option<T> {
+        self.data
+    }
+}
+
+
+/// A `{ code, data, msg }` style response wrapper.
+///
+/// The API reports business failures through `code` while still answering
+/// HTTP 200; [`Envelope::into_result`] turns those into
+/// [`OpenoceanError::Api`] and hands back the inner data otherwise.
+pub trait Envelope: Sized {
+    type Data;
+
+    fn code(&self) -> i32;
+
+    fn message(&self) -> Option<&str>;
+
+    fn into_data(self) -> Option<Self::Data>;
+
+    fn is_success(&self) -> bool {
+        is_success_code(self.code())
+    }
+
+    /// Checks the code and returns the data, which may legitimately be absent.
+    fn check(self, endpoint: &str) -> Result<Option<Self::Data>, OpenoceanError> {
+        if self.is_success() {
+            Ok(self.into_data())
+        } else {
+            Err(api_error(self.code(), self.message().unwrap_or_default(), endpoint))
+        }
+    }
+
+    /// Checks the code and returns the data, failing if it is absent.
+    fn into_result(self, endpoint: &str) -> Result<Self::Data, OpenoceanError> {
+        let code = self.code();
+        self.check(endpoint)?.ok_or_else(|| OpenoceanError::Api {
+            code,
+            kind: ApiErrorKind::MissingData,
+            message: "response has no data".to_string(),
+            endpoint: endpoint.to_string(),
+        })
+    }
+}
+
+pub(crate) fn is_success_code(code: i32) -> bool {
+    code == 200 || code == 0
+}
+
+pub(crate) fn api_error(code: i32, message: &str, endpoint: &str) -> OpenoceanError {
+    OpenoceanError::Api {
+        code,
+        kind: ApiErrorKind::classify(code, message),
+        message: message.to_string(),
+        endpoint: endpoint.to_string(),
+    }
+}
+
+/// Just the status part of any envelope, used to surface API errors for
+/// bodies that do not match the expected data type.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EnvelopeStatus {
+    pub code: i32,
+    #[serde(alias = "msg", alias = "message", alias = "error")]
+    pub error_msg: Option<String>,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_result() {
+        let ok: BaseResponse<Vec<i32>> = serde_json::from_str(r#"{"code":200,"data":[1,2]}"#).unwrap();
+        assert_eq!(ok.into_result("/v4/bsc/dexList").unwrap(), vec![1, 2]);
+
+        let failed: BaseResponse<Vec<i32>> =
+            serde_json::from_str(r#"{"code":500,"data":null,"errorMsg":"no liquidity"}"#).unwrap();
+        match failed.into_result("/v4/bsc/quote") {
+            Err(OpenoceanError::Api { code, kind, endpoint, .. }) => {
+                assert_eq!(code, 500);
+                assert_eq!(kind, ApiErrorKind::InsufficientLiquidity);
+                assert_eq!(endpoint, "/v4/bsc/quote");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        let empty: BaseResponse<()> = serde_json::from_str(r#"{"code":200}"#).unwrap();
+        assert!(empty.check("/v2/bsc/limit-order").unwrap().is_none());
+    }
+}
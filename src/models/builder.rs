@@ -0,0 +1,186 @@
+/// Generates a `XxxBuilder` for a params struct.
+///
+/// Setters accept anything that converts into the field type via `TryInto`;
+/// conversion failures and missing required fields are reported by `build()`
+/// as [`OpenoceanError::InvalidParams`](crate::OpenoceanError::InvalidParams).
+///
+/// - `required` fields must be set before `build()`.
+/// - `defaults` fields fall back to the given expression.
+/// - `optional` fields map onto `Option<T>` struct fields and stay `None` if unset.
+macro_rules! params_builder {
+    (
+        $name:ident => $builder:ident {
+            required { $($req:ident : $req_ty:ty),* $(,)? }
+            defaults { $($def:ident : $def_ty:ty = $def_val:expr),* $(,)? }
+            optional { $($opt:ident : $opt_ty:ty),* $(,)? }
+        }
+    ) => {
+        #[doc = concat!("Builder for [`", stringify!($name), "`].")]
+        #[derive(Debug, Clone, Default)]
+        pub struct $builder {
+            $( $req: Option<Result<$req_ty, String>>, )*
+            $( $def: Option<Result<$def_ty, String>>, )*
+            $( $opt: Option<Result<$opt_ty, String>>, )*
+        }
+
+        impl $name {
+            pub fn builder() -> $builder {
+                $builder::default()
+            }
+        }
+
+        impl $builder {
+            $(
+                pub fn $req<V>(mut self, value: V) -> Self
+                where
+                    V: TryInto<$req_ty>,
+                    V::Error: std::fmt::Display,
+                {
+                    self.$req = Some(value.try_into().map_err(|e| e.to_string()));
+                    self
+                }
+            )*
+
+            $(
+                pub fn $def<V>(mut self, value: V) -> Self
+                where
+                    V: TryInto<$def_ty>,
+                    V::Error: std::fmt::Display,
+                {
+                    self.$def = Some(value.try_into().map_err(|e| e.to_string()));
+                    self
+                }
+            )*
+
+            $(
+                pub fn $opt<V>(mut self, value: V) -> Self
+                where
+                    V: TryInto<$opt_ty>,
+                    V::Error: std::fmt::Display,
+                {
+                    self.$opt = Some(value.try_into().map_err(|e| e.to_string()));
+                    self
+                }
+            )*
+
+            pub fn build(self) -> Result<$name, $crate::OpenoceanError> {
+                let invalid = |field: &str, message: String| {
+                    $crate::OpenoceanError::InvalidParams(format!(
+                        "{}.{}: {}", stringify!($name), field, message,
+                    ))
+                };
+
+                Ok($name {
+                    $(
+                        $req: match self.$req {
+                            Some(v) => v.map_err(|e| invalid(stringify!($req), e))?,
+                            None => return Err(invalid(stringify!($req), "missing required field".to_string())),
+                        },
+                    )*
+                    $(
+                        $def: match self.$def {
+                            Some(v) => v.map_err(|e| invalid(stringify!($def), e))?,
+                            None => $def_val,
+                        },
+                    )*
+                    $(
+                        $opt: self.$opt
+                            .transpose()
+                            .map_err(|e| invalid(stringify!($opt), e))?,
+                    )*
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{models::{limit_order::CreateLimitOrderParams, swap::QuoteParams}, types::Timestamp, OpenoceanError};
+
+    #[test]
+    fn test_build_quote_params() {
+        let params = QuoteParams::builder()
+            .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+            .out_token_address("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+            .amount_decimals("5000000000000000000")
+            .gas_price_decimals("1000000000")
+            .enabled_dex_ids([1, 2])
+            .build()
+            .unwrap();
+
+        assert_eq!(params.slippage, None);
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["enabledDexIds"], "1,2");
+    }
+
+    #[test]
+    fn test_build_missing_required_field() {
+        let err = QuoteParams::builder()
+            .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, OpenoceanError::InvalidParams(ref msg) if msg.contains("QuoteParams.out_token_address")));
+    }
+
+    #[test]
+    fn test_build_rejects_out_of_range_slippage() {
+        let err = QuoteParams::builder()
+            .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+            .out_token_address("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+            .amount_decimals("5000000000000000000")
+            .gas_price_decimals("1000000000")
+            .slippage(80)
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, OpenoceanError::InvalidParams(ref msg) if msg.contains("QuoteParams.slippage")));
+    }
+
+    #[test]
+    fn test_build_limit_order_expiry_in_unix_seconds() {
+        let params = CreateLimitOrderParams::builder()
+            .taker_asset("0x55d398326f99059ff775485246999027b3197955")
+            .maker_asset("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+            .expire_time(Timestamp::from_unix_millis(1714564800999))
+            .order_maker("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f")
+            .signature("0x01")
+            .taker_amount("1000")
+            .maker_amount("1000")
+            .build()
+            .unwrap();
+
+        assert_eq!(serde_json::to_value(&params).unwrap()["expireTime"], 1714564800);
+    }
+
+    #[test]
+    fn test_build_ticket_quote_through_api_module() {
+        let quote = crate::ticket::Quote::builder()
+            .quote_type("swap")
+            .in_token_symbol("USDT")
+            .in_token_address("0x55d398326f99059ff775485246999027b3197955")
+            .out_token_symbol("USDC")
+            .out_token_address("0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d")
+            .amount("5")
+            .gas_price("1")
+            .disabled_dex_ids([3])
+            .build()
+            .unwrap();
+
+        assert_eq!(quote.amount_all, 0);
+        let json = serde_json::to_value(&quote).unwrap();
+        assert_eq!(json["disabledDexIds"], "3");
+        assert_eq!(json["referrer"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_build_out_token_params() {
+        let err = crate::sweep_swap::OutTokenParams::builder()
+            .out_token_symbol("USDC")
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, OpenoceanError::InvalidParams(ref msg) if msg.contains("OutTokenParams.out_token_address")));
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::models::base::BaseResponse;
+
+
+
+
+/// A chain as listed by the API.
+///
+/// Only `code`, `chainId` and `name` are read; other fields such as an
+/// internal `id` are ignored so they can never be taken for an EVM chain id.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedChain {
+    /// Code used in API paths, e.g. `bsc`.
+    pub code: String,
+    #[serde(default, deserialize_with = "de_opt_chain_id")]
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Chain ids come as numbers or numeric strings; anything else (e.g.
+/// Starknet's `SN_MAIN`) is treated as "no EVM chain id".
+fn de_opt_chain_id<'de, D>(de: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(de)? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    })
+}
+
+pub type GetChainListResponse = BaseResponse<Vec<SupportedChain>>;
@@ -1,25 +1,29 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{formats::CommaSeparator, serde_as, DurationSeconds, StringWithSeparator};
 
-use crate::models::gasless::BaseResponse;
+use crate::{models::{base::Envelope, gasless::BaseResponse}, types::{Bytes, Percent, Timestamp, TokenAmount, U256}, Address};
 
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct DcaCreateSwapParams {
-    pub maker_amount: String,
-    pub signature: String,
-    pub order_maker: String,
-    pub maker_asset: String,
-    pub taker_asset: String,
-    pub time: i64,
+    pub maker_amount: U256,
+    pub signature: Bytes,
+    pub order_maker: Address,
+    pub maker_asset: Address,
+    pub taker_asset: Address,
+    /// Interval between two swaps.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub time: Duration,
     pub times: i64,
     pub min_price: String,
     pub max_price: String,
-    pub referrer: String,
-    pub referrer_fee: String,
+    pub referrer: Address,
+    pub referrer_fee: Percent,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "enabledDexIds",  skip_serializing_if = "Option::is_none")]
     pub enabled_dex_ids: Option<Vec<i32>>,
@@ -28,18 +32,69 @@ pub struct DcaCreateSwapParams {
     pub disabled_dex_ids: Option<Vec<i32>>, 
 }
 
+params_builder! {
+    DcaCreateSwapParams => DcaCreateSwapParamsBuilder {
+        required {
+            maker_amount: U256,
+            signature: Bytes,
+            order_maker: Address,
+            maker_asset: Address,
+            taker_asset: Address,
+            time: Duration,
+            times: i64,
+        }
+        defaults {
+            min_price: String = String::new(),
+            max_price: String = String::new(),
+            referrer: Address = Address::zero(),
+            referrer_fee: Percent = Percent::ZERO,
+        }
+        optional {
+            enabled_dex_ids: Vec<i32>,
+            disabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DcaCreateSwapResponse {
     pub code: i32,
 }
 
+impl Envelope for DcaCreateSwapResponse {
+    type Data = ();
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        None
+    }
+
+    fn into_data(self) -> Option<()> {
+        Some(())
+    }
+}
+
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DcaCancelOrderParams {
     pub order_hash: String,
-    pub signature: String
+    pub signature: Bytes
+}
+
+params_builder! {
+    DcaCancelOrderParams => DcaCancelOrderParamsBuilder {
+        required {
+            order_hash: String,
+            signature: Bytes,
+        }
+        defaults {}
+        optional {}
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -48,20 +103,38 @@ pub struct DcaCancelSwapResponse {
     pub code: i32,
 }
 
+impl Envelope for DcaCancelSwapResponse {
+    type Data = ();
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        None
+    }
+
+    fn into_data(self) -> Option<()> {
+        Some(())
+    }
+}
+
 
 pub type GetDcaOrdersResponse = BaseResponse<Vec<DcaOrder>>;
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DcaOrder {
-    pub maker_amount: String,
-    pub taker_amount: String,
+    pub maker_amount: U256,
+    pub taker_amount: U256,
     pub order_hash: String,
-    pub create_date_time: String,
-    pub order_maker: String,
-    pub expire_time: String,
+    pub create_date_time: Timestamp,
+    pub order_maker: Address,
+    pub expire_time: Timestamp,
     pub statuses: i32,
-    pub time: i64,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub time: Duration,
     pub times: i64,
     pub have_filled: Option<String>,
     pub min_price: Option<String>,
@@ -69,14 +142,24 @@ pub struct DcaOrder {
     pub data: DcaOrderData,
 }
 
+impl DcaOrder {
+    pub fn maker_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.maker_amount, self.data.maker_asset_decimals as u8)
+    }
+
+    pub fn taker_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.taker_amount, self.data.taker_asset_decimals as u8)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DcaOrderData {
-    pub maker_asset: String,
+    pub maker_asset: Address,
     pub maker_asset_symbol: String,
     pub maker_asset_decimals: i32,
     pub maker_asset_icon: String,
-    pub taker_asset: String,
+    pub taker_asset: Address,
     pub taker_asset_symbol: String,
     pub taker_asset_decimals: i32,
     pub taker_asset_icon: String,
@@ -90,7 +173,7 @@ pub type GetDcaOrderFillsResponse = BaseResponse<Vec<DcaOrderFill>>;
 pub struct DcaOrderFill {
     pub order_hash: String,
     pub tx_hash: String,
-    pub filled_order_time: String,
+    pub filled_order_time: Timestamp,
     pub payment: String,
     pub payment_value: String,
     pub status: String,
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
 
-use crate::{models::swap::QuoteToken, Chain};
+use crate::{models::{base::Envelope, swap::QuoteToken}, types::{unix_seconds, Bytes, Slippage, Timestamp, TokenAmount, U256}, Address, Chain};
 
 
 
@@ -13,30 +13,64 @@ pub struct BaseResponse<T> {
     pub msg: Option<String>,
 }
 
+impl<T> Envelope for BaseResponse<T> {
+    type Data = T;
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        self.msg.as_deref()
+    }
+
+    fn into_data(self) -> Option<T> {
+        self.data
+    }
+}
+
 
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GaslessQuoteParams {
     pub chain: String,
-    pub in_token_address: String,
-    pub out_token_address: String,
-    pub amount_decimals: String,
-    pub gas_price_decimals: String,
-    pub slippage: Option<String>,
-    pub referrer: Option<String>,
+    pub in_token_address: Address,
+    pub out_token_address: Address,
+    pub amount_decimals: U256,
+    pub gas_price_decimals: U256,
+    pub slippage: Option<Slippage>,
+    pub referrer: Option<Address>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
 }
 
+params_builder! {
+    GaslessQuoteParams => GaslessQuoteParamsBuilder {
+        required {
+            chain: String,
+            in_token_address: Address,
+            out_token_address: Address,
+            amount_decimals: U256,
+            gas_price_decimals: U256,
+        }
+        defaults {}
+        optional {
+            slippage: Slippage,
+            referrer: Address,
+            disabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteFee {
-    pub address: String,
+    pub address: Address,
     pub decimals: u8,
     pub symbol: String,
     pub name: String,
@@ -48,8 +82,8 @@ pub struct QuoteFee {
 
 pub type GaslessQuoteResponse = BaseResponse<GaslessQuoteData>;
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GaslessQuoteData {
     pub in_token: QuoteToken,
@@ -57,18 +91,28 @@ pub struct GaslessQuoteData {
     pub native: QuoteToken,
     pub fees: Vec<QuoteFee>,
     pub flag: i32,
-    pub in_amount: String,
-    pub out_amount: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
     pub estimated_gas: i64,
     pub path: QuotePath,
 }
 
+impl GaslessQuoteData {
+    pub fn in_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.in_amount, self.in_token.decimals)
+    }
+
+    pub fn out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.out_amount, self.out_token.decimals)
+    }
+}
+
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuotePath {
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub parts: u32,
     #[serde(default)]
     pub routes: Vec<QuoteRoute>,
@@ -86,8 +130,8 @@ pub struct QuoteRoute {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteSubRoute {
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub parts: u32,
     #[serde(default)]
     pub dexes: Vec<QuoteSubRouteDex>,
@@ -108,23 +152,48 @@ pub struct QuoteSubRouteDex {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GaslessSwapParams {
-    pub from: String,
-    pub to: String,
-    pub data: String,
-    pub amount_decimals: String,
-    pub fee_amount1: String,
-    pub fee_amount2: String,
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub amount_decimals: U256,
+    pub fee_amount1: U256,
+    pub fee_amount2: U256,
     pub flag: i32,
-    pub gas_price_decimals: i32,
-    pub deadline: i64,
-    pub in_token: String,
-    pub out_token: String,
+    pub gas_price_decimals: U256,
+    #[serde(with = "unix_seconds")]
+    pub deadline: Timestamp,
+    pub in_token: Address,
+    pub out_token: Address,
     pub nonce: i64,
-    pub permit: String,
+    pub permit: Bytes,
     #[serde(rename = "usdvaluation")]
     pub usdvaluation: f64,
 }
 
+params_builder! {
+    GaslessSwapParams => GaslessSwapParamsBuilder {
+        required {
+            from: Address,
+            to: Address,
+            data: Bytes,
+            amount_decimals: U256,
+            fee_amount1: U256,
+            fee_amount2: U256,
+            flag: i32,
+            gas_price_decimals: U256,
+            deadline: Timestamp,
+            in_token: Address,
+            out_token: Address,
+            permit: Bytes,
+        }
+        defaults {
+            nonce: i64 = 0,
+            usdvaluation: f64 = 0.0,
+        }
+        optional {}
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GaslessSwapResponse {
@@ -133,6 +202,22 @@ pub struct GaslessSwapResponse {
     pub order_hash: Option<String>,
 }
 
+impl Envelope for GaslessSwapResponse {
+    type Data = String;
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        self.msg.as_deref()
+    }
+
+    fn into_data(self) -> Option<String> {
+        self.order_hash
+    }
+}
+
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -141,6 +226,17 @@ pub struct GetOrderStatusParams {
     pub order_hash: String,
 }
 
+params_builder! {
+    GetOrderStatusParams => GetOrderStatusParamsBuilder {
+        required {
+            chain: Chain,
+            order_hash: String,
+        }
+        defaults {}
+        optional {}
+    }
+}
+
 
 pub type GetOrderStatusResponse = BaseResponse<GetOrderStatusData>;
 
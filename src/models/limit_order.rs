@@ -1,24 +1,25 @@
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
 
-use crate::{models::base::BaseResponse};
+use crate::{models::base::BaseResponse, types::{unix_seconds, Bytes, Percent, Timestamp, TokenAmount, U256}, Address};
 
 
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct CreateLimitOrderParams {
-    pub taker_asset: String,
-    pub maker_asset: String,
-    pub expire_time: String,
-    pub order_maker: String,
-    pub signature: String,
-    pub taker_amount: String,
-    pub maker_amount: String,
-    pub referrer: Option<String>,
-    pub referrer_fee: Option<String>,
+    pub taker_asset: Address,
+    pub maker_asset: Address,
+    #[serde(with = "unix_seconds")]
+    pub expire_time: Timestamp,
+    pub order_maker: Address,
+    pub signature: Bytes,
+    pub taker_amount: U256,
+    pub maker_amount: U256,
+    pub referrer: Option<Address>,
+    pub referrer_fee: Option<Percent>,
 
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "enabledDexIds",  skip_serializing_if = "Option::is_none")]
@@ -29,42 +30,85 @@ pub struct CreateLimitOrderParams {
     pub disabled_dex_ids: Option<Vec<i32>>,
 }
 
+params_builder! {
+    CreateLimitOrderParams => CreateLimitOrderParamsBuilder {
+        required {
+            taker_asset: Address,
+            maker_asset: Address,
+            expire_time: Timestamp,
+            order_maker: Address,
+            signature: Bytes,
+            taker_amount: U256,
+            maker_amount: U256,
+        }
+        defaults {}
+        optional {
+            referrer: Address,
+            referrer_fee: Percent,
+            enabled_dex_ids: Vec<i32>,
+            disabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 pub type CreateLimitOrderResponse = BaseResponse<()>;
 
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct CancelLimitOrderParams {
     pub order_hash: String,
-    pub signature: String,
+    pub signature: Bytes,
+}
+
+params_builder! {
+    CancelLimitOrderParams => CancelLimitOrderParamsBuilder {
+        required {
+            order_hash: String,
+            signature: Bytes,
+        }
+        defaults {}
+        optional {}
+    }
 }
 
 pub type CancelLimitOrderResponse = BaseResponse<()>;
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct GetLimitOrdersByAddressParams {
     pub statuses: String,
     pub limit: i32,
 }
 
+params_builder! {
+    GetLimitOrdersByAddressParams => GetLimitOrdersByAddressParamsBuilder {
+        required {}
+        defaults {
+            statuses: String = "[1,2,5]".to_string(),
+            limit: i32 = 100,
+        }
+        optional {}
+    }
+}
+
 
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LimitOrder {
-    pub maker_amount: String,
-    pub taker_amount: String,
-    pub signature: String,
+    pub maker_amount: U256,
+    pub taker_amount: U256,
+    pub signature: Bytes,
     pub order_hash: String,
-    pub create_date_time: String,
-    pub order_maker: String,
-    pub remaining_maker_amount: String,
-    pub expire_time: String,
+    pub create_date_time: Timestamp,
+    pub order_maker: Address,
+    pub remaining_maker_amount: U256,
+    pub expire_time: Timestamp,
     pub statuses: i32,
     pub data: LimitOrderData,
     pub maker_rate: String,
@@ -73,30 +117,44 @@ pub struct LimitOrder {
     pub referrer_fee: Option<String>,
 }
 
+impl LimitOrder {
+    pub fn maker_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.maker_amount, self.data.maker_asset_decimals as u8)
+    }
+
+    pub fn taker_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.taker_amount, self.data.taker_asset_decimals as u8)
+    }
+
+    pub fn remaining_maker_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.remaining_maker_amount, self.data.maker_asset_decimals as u8)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LimitOrderData {
-    pub maker_asset: String,
+    pub maker_asset: Address,
     pub maker_asset_symbol: String,
     pub maker_asset_decimals: i32,
     pub maker_asset_icon: String,
-    pub taker_asset: String,
+    pub taker_asset: Address,
     pub taker_asset_symbol: String,
     pub taker_asset_decimals: i32,
     pub taker_asset_icon: String,
     pub get_maker_amount: String,
     pub get_taker_amount: String,
-    pub maker_asset_data: String,
-    pub taker_asset_data: String,
+    pub maker_asset_data: Bytes,
+    pub taker_asset_data: Bytes,
     pub salt: String,
-    pub permit: String,
-    pub predicate: String,
-    pub interaction: String,
-    pub making_amount: String,
-    pub taking_amount: String,
-    pub maker: String,
-    pub receiver: String,
-    pub allowed_sender: String,
+    pub permit: Bytes,
+    pub predicate: Bytes,
+    pub interaction: Bytes,
+    pub making_amount: U256,
+    pub taking_amount: U256,
+    pub maker: Address,
+    pub receiver: Address,
+    pub allowed_sender: Address,
 }
 
 
@@ -1,3 +1,12 @@
+//! Request and response types for the OpenOcean APIs.
+//!
+//! Each submodule mirrors an API handle (`swap` for [`Swap`](crate::Swap),
+//! `gasless` for [`Gasless`](crate::Gasless), ...). Params structs come with
+//! a `builder()` that checks required fields and fills defaults.
+
+#[macro_use]
+mod builder;
+
 pub mod base;
 pub mod swap;
 pub mod gasless;
@@ -5,4 +14,5 @@ pub mod dca;
 pub mod limit_order;
 pub mod zap;
 pub mod sweep_swap;
-pub mod ticket;
\ No newline at end of file
+pub mod ticket;
+pub mod chains;
@@ -1,30 +1,7 @@
-use serde::{Deserialize, Deserializer, Serialize};
-use serde_json::Number;
-use serde_with::serde_as;
+use serde::{Deserialize, Serialize};
+use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
 
-use crate::{models::base::BaseResponse, types::U128};
-
-
-
-
-/// 允许同时接受 JSON string 或 number，并统一转成 String
-#[derive(Deserialize)]
-#[serde(untagged)]
-enum NumOrStr {
-    N(Number),
-    S(String),
-}
-
-fn de_num_or_str_to_string<'de, D>(de: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let v: NumOrStr = NumOrStr::deserialize(de)?;
-    Ok(match v {
-        NumOrStr::N(n) => n.to_string(), // e.g. "1.0106346115864016e21"
-        NumOrStr::S(s) => s,
-    })
-}
+use crate::{models::base::{BaseResponse, Envelope}, types::{Bytes, Percent, Slippage, Timestamp, TokenAmount, U256}, Address};
 
 
 
@@ -36,13 +13,13 @@ pub struct Token {
     pub id: i32,
     pub code: String,
     pub name: String,
-    pub address: String,
+    pub address: Address,
     pub decimals: u8,
     pub symbol: String,
     pub icon: String,
     pub chain: String,
     #[serde(rename = "createtime")]
-    pub create_time: String,
+    pub create_time: Timestamp,
     pub chain_id: Option<i32>,
     pub custom_symbol: Option<String>,
     pub custom_address: Option<String>,
@@ -67,17 +44,33 @@ pub struct GasResponse {
     pub without_decimals: GasPrice,
 }
 
+impl Envelope for GasResponse {
+    type Data = GasPrice;
+
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn message(&self) -> Option<&str> {
+        None
+    }
+
+    fn into_data(self) -> Option<GasPrice> {
+        Some(self.data)
+    }
+}
+
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct QuoteParams {
-    pub in_token_address: String,
-    pub out_token_address: String,
-    pub amount_decimals: String,
-    pub gas_price_decimals: String,
-    pub slippage: Option<String>,
+    pub in_token_address: Address,
+    pub out_token_address: Address,
+    pub amount_decimals: U256,
+    pub gas_price_decimals: U256,
+    pub slippage: Option<Slippage>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
@@ -86,6 +79,23 @@ pub struct QuoteParams {
     pub enabled_dex_ids: Option<Vec<i32>>,
 }
 
+params_builder! {
+    QuoteParams => QuoteParamsBuilder {
+        required {
+            in_token_address: Address,
+            out_token_address: Address,
+            amount_decimals: U256,
+            gas_price_decimals: U256,
+        }
+        defaults {}
+        optional {
+            slippage: Slippage,
+            disabled_dex_ids: Vec<i32>,
+            enabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 
 pub type QuoteResponse = BaseResponse<QuoteData>;
 
@@ -95,22 +105,32 @@ pub type QuoteResponse = BaseResponse<QuoteData>;
 pub struct QuoteData {
     pub in_token: QuoteToken,
     pub out_token: QuoteToken,
-    pub in_amount: String,
-    pub out_amount: String,
-    pub estimated_gas: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
+    pub estimated_gas: U256,
     #[serde(default)]
     pub dexes: Vec<QuoteDex>,
     pub path: QuotePath,
     pub save: f64,
     #[serde(rename = "price_impact")]
-    pub price_impact: String, // e.g.: "0.01%"
+    pub price_impact: Percent, // e.g.: "0.01%"
     pub exchange: String,
 }
 
+impl QuoteData {
+    pub fn in_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.in_amount, self.in_token.decimals)
+    }
+
+    pub fn out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.out_amount, self.out_token.decimals)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteToken {
-    pub address: String,
+    pub address: Address,
     pub decimals: u8,
     pub symbol: String,
     pub name: String,
@@ -123,14 +143,14 @@ pub struct QuoteToken {
 pub struct QuoteDex {
     pub dex_index: i32,
     pub dex_code: String,
-    pub swap_amount: String,
+    pub swap_amount: U256,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuotePath {
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub parts: u32,
     #[serde(default)]
     pub routes: Vec<QuoteRoute>,
@@ -148,8 +168,8 @@ pub struct QuoteRoute {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuoteSubRoute {
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub parts: u32,
     #[serde(default)]
     pub dexes: Vec<QuoteSubRouteDex>,
@@ -166,15 +186,15 @@ pub struct QuoteSubRouteDex {
 
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct ReverseQuoteParams {
-    pub in_token_address: String,
-    pub out_token_address: String,
-    pub amount: String,
-    pub gas_price: String,
-    pub slippage: Option<String>,
+    pub in_token_address: Address,
+    pub out_token_address: Address,
+    pub amount: U256,
+    pub gas_price: U256,
+    pub slippage: Option<Slippage>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
@@ -183,6 +203,23 @@ pub struct ReverseQuoteParams {
     pub enabled_dex_ids: Option<Vec<i32>>,
 }
 
+params_builder! {
+    ReverseQuoteParams => ReverseQuoteParamsBuilder {
+        required {
+            in_token_address: Address,
+            out_token_address: Address,
+            amount: U256,
+            gas_price: U256,
+        }
+        defaults {}
+        optional {
+            slippage: Slippage,
+            disabled_dex_ids: Vec<i32>,
+            enabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 
 
 pub type ReverseQuoteResponse = BaseResponse<ReverseQuoteData>;
@@ -193,17 +230,27 @@ pub type ReverseQuoteResponse = BaseResponse<ReverseQuoteData>;
 pub struct ReverseQuoteData {
     pub in_token: QuoteToken,
     pub out_token: QuoteToken,
-    pub in_amount: String,
-    pub out_amount: String,
-    pub estimated_gas: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
+    pub estimated_gas: U256,
     #[serde(default)]
     pub dexes: Vec<QuoteDex>,
     pub path: QuotePath,
     pub save: f64,
     #[serde(rename = "price_impact")]
-    pub price_impact: String, // e.g.: "0.01%"
-    #[serde(rename = "reverseAmount", deserialize_with = "de_num_or_str_to_string")]
-    pub reverse_amount: String,
+    pub price_impact: Percent, // e.g.: "0.01%"
+    #[serde(rename = "reverseAmount")]
+    pub reverse_amount: U256,
+}
+
+impl ReverseQuoteData {
+    pub fn in_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.in_amount, self.in_token.decimals)
+    }
+
+    pub fn out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.out_amount, self.out_token.decimals)
+    }
 }
 
 
@@ -211,28 +258,50 @@ pub struct ReverseQuoteData {
 
 
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapQuoteParams {
-    pub in_token_address: String,
-    pub out_token_address: String,
-    pub amount_decimals: String,
-    pub gas_price_decimals: String,
-    pub slippage: Option<String>,
-    pub account: String,
-    pub referrer: Option<String>,
-    pub referrer_fee: Option<f64>,
+    pub in_token_address: Address,
+    pub out_token_address: Address,
+    pub amount_decimals: U256,
+    pub gas_price_decimals: U256,
+    pub slippage: Option<Slippage>,
+    pub account: Address,
+    pub referrer: Option<Address>,
+    pub referrer_fee: Option<Percent>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "enabledDexIds",  skip_serializing_if = "Option::is_none")]
     pub enabled_dex_ids: Option<Vec<i32>>,
-    pub sender: Option<String>,
+    pub sender: Option<Address>,
     pub mint_output: Option<u64>,
 }
 
+params_builder! {
+    SwapQuoteParams => SwapQuoteParamsBuilder {
+        required {
+            in_token_address: Address,
+            out_token_address: Address,
+            amount_decimals: U256,
+            gas_price_decimals: U256,
+            account: Address,
+        }
+        defaults {}
+        optional {
+            slippage: Slippage,
+            referrer: Address,
+            referrer_fee: Percent,
+            disabled_dex_ids: Vec<i32>,
+            enabled_dex_ids: Vec<i32>,
+            sender: Address,
+            mint_output: u64,
+        }
+    }
+}
+
 
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -241,21 +310,34 @@ pub struct SwapQuoteData {
     pub in_token: QuoteToken,
     pub out_token: QuoteToken,
 
-    pub in_amount: String,
-    pub out_amount: String,
-    #[serde(deserialize_with = "de_num_or_str_to_string")]
-    pub estimated_gas: String,
-    pub min_out_amount: String,
-    pub from: String,
-    pub to: String,
-    pub value: String,
-    pub gas_price: String,
-    pub data: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
+    pub estimated_gas: U256,
+    pub min_out_amount: U256,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub gas_price: U256,
+    pub data: Bytes,
     pub chain_id: i32,
     pub rfq_dealine: Option<i32>,
     pub gmx_fee: i32,
     #[serde(rename = "price_impact")]
-    pub price_impact: String,
+    pub price_impact: Percent,
+}
+
+impl SwapQuoteData {
+    pub fn in_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.in_amount, self.in_token.decimals)
+    }
+
+    pub fn out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.out_amount, self.out_token.decimals)
+    }
+
+    pub fn min_out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.min_out_amount, self.out_token.decimals)
+    }
 }
 
 pub type SwapQuoteResponse = BaseResponse<SwapQuoteData>;
@@ -280,23 +362,23 @@ pub struct Transaction {
     pub tx_id: Option<String>,
     pub block_number: u64,
     pub tx_index: u64,
-    pub address: String,
+    pub address: Address,
     pub tx_hash: String,
     pub tx_hash_url: String,
-    pub sender: String,
-    pub receiver: String,
-    pub in_token_address: String,
+    pub sender: Address,
+    pub receiver: Address,
+    pub in_token_address: Address,
     pub in_token_symbol: String,
-    pub out_token_address: String,
+    pub out_token_address: Address,
     pub out_token_symbol: String,
     pub referrer: String,
-    pub in_amount: String,
-    pub out_amount: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
     pub fee: Option<String>,
     pub referrer_fee: Option<String>,
     pub usd_valuation: f64,
-    pub create_at: String,
-    pub update_at: String,
+    pub create_at: Timestamp,
+    pub update_at: Timestamp,
     pub tx_fee: String,
     pub tx_fee_valuation: String,
     pub in_token_decimals: u8,
@@ -310,12 +392,22 @@ pub struct Transaction {
 }
 
 
+impl Transaction {
+    pub fn in_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.in_amount, self.in_token_decimals)
+    }
+
+    pub fn out_token_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.out_amount, self.out_token_decimals)
+    }
+}
+
 pub type GetTransactionResponse = BaseResponse<Transaction>;
 
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DecodeInputDataResponse {
-    pub caller: String,
+    pub caller: Address,
     pub desc: SwapDesc,
     pub calls: Vec<CallStep>,
 }
@@ -323,26 +415,26 @@ pub struct DecodeInputDataResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapDesc {
-    pub src_token: String,
-    pub dst_token: String,
-    pub src_receiver: String,
-    pub dst_receiver: String,
-
-    pub amount: String,
-    pub min_return_amount: String,
-    pub guaranteed_amount: String,
+    pub src_token: Address,
+    pub dst_token: Address,
+    pub src_receiver: Address,
+    pub dst_receiver: Address,
+
+    pub amount: U256,
+    pub min_return_amount: U256,
+    pub guaranteed_amount: U256,
     pub flags: String,
     pub referrer: String,
-    pub permit: String,
+    pub permit: Bytes,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallStep {
-    pub target: String,
-    pub gas_limit: String,
-    pub value: String,
-    pub data: String,
+    pub target: Address,
+    pub gas_limit: U256,
+    pub value: U256,
+    pub data: Bytes,
 }
 
 
@@ -356,8 +448,25 @@ pub struct GasPriceResponse {
     pub error_msg: Option<String>,
 }
 
+impl Envelope for GasPriceResponse {
+    type Data = GasPriceData;
+
+    fn code(&self) -> i32 {
+        self.code as i32
+    }
+
+    fn message(&self) -> Option<&str> {
+        self.error_msg.as_deref()
+    }
+
+    fn into_data(self) -> Option<GasPriceData> {
+        self.data
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum GasPriceData {
     NonEvm(GasPriceDataNonEvm),
     Evm(GasPriceDataEvm),
@@ -373,18 +482,48 @@ pub struct GasPriceDataEvm {
     pub low: GasPriceTierInt,
 }
 
+impl GasPriceData {
+    pub fn evm(&self) -> Option<&GasPriceDataEvm> {
+        match self {
+            GasPriceData::Evm(evm) => Some(evm),
+            GasPriceData::NonEvm(_) => None,
+        }
+    }
+}
+
+/// Speed tier of an EVM gas price quote.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GasTier {
+    Low,
+    #[default]
+    Standard,
+    Fast,
+    Instant,
+}
+
+impl GasPriceDataEvm {
+    pub fn tier(&self, tier: GasTier) -> &GasPriceTierInt {
+        match tier {
+            GasTier::Low => &self.low,
+            GasTier::Standard => &self.standard,
+            GasTier::Fast => &self.fast,
+            GasTier::Instant => &self.instant,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GasPriceTierInt {
-    pub legacy_gas_price: U128,
-    pub max_priority_fee_per_gas: U128,
-    pub max_fee_per_gas: U128,
-    pub wait_time_estimate: U128,
+    pub legacy_gas_price: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub wait_time_estimate: U256,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GasPriceDataNonEvm {
-    pub standard: U128,
-    pub fast: U128,
-    pub instant: U128,
+    pub standard: U256,
+    pub fast: U256,
+    pub instant: U256,
 }
\ No newline at end of file
@@ -1,72 +1,125 @@
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{formats::CommaSeparator, serde_as, DisplayFromStr, PickFirst, StringWithSeparator};
 
+use crate::{types::{Bytes, Slippage, U256}, Address};
 
 
 
 
+
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct MultiSwapQuoteParams {
     pub in_token: InTokenParams,
     pub out_token: OutTokenParams,
     pub gas_price: f64,
-    pub referrer: Option<String>,
+    pub referrer: Option<Address>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
-    pub account: String,
+    pub account: Address,
+}
+
+params_builder! {
+    MultiSwapQuoteParams => MultiSwapQuoteParamsBuilder {
+        required {
+            in_token: InTokenParams,
+            out_token: OutTokenParams,
+            gas_price: f64,
+            account: Address,
+        }
+        defaults {}
+        optional {
+            referrer: Address,
+            disabled_dex_ids: Vec<i32>,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InTokenParams {
     pub in_token_symbol: String,
-    pub in_token_address: String,
-    pub amount: String,
-    pub slippage: u16,
+    pub in_token_address: Address,
+    pub amount: U256,
+    pub slippage: Slippage,
+}
+
+params_builder! {
+    InTokenParams => InTokenParamsBuilder {
+        required {
+            in_token_symbol: String,
+            in_token_address: Address,
+            amount: U256,
+        }
+        defaults {
+            slippage: Slippage = Slippage::DEFAULT,
+        }
+        optional {}
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OutTokenParams {
     pub out_token_symbol: String,
-    pub out_token_address: String,
+    pub out_token_address: Address,
+}
+
+params_builder! {
+    OutTokenParams => OutTokenParamsBuilder {
+        required {
+            out_token_symbol: String,
+            out_token_address: Address,
+        }
+        defaults {}
+        optional {}
+    }
 }
 
+impl OutTokenParams {
+    pub fn new(out_token_symbol: impl Into<String>, out_token_address: Address) -> Self {
+        Self {
+            out_token_symbol: out_token_symbol.into(),
+            out_token_address,
+        }
+    }
+}
+
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct MultiSwapQuoteResponse {
     pub in_token: Vec<Token>,
     pub out_token: Token,
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub swap: Vec<Swap>,
-    pub gas_price: String,
-    pub chain_id: String,
-    pub value: String,
-    pub data: String,
+    pub gas_price: U256,
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    pub chain_id: u64,
+    pub value: U256,
+    pub data: Bytes,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct Token {
-    pub address: String,
+    pub address: Address,
     pub decimals: u8,
     pub symbol: String,
     pub name: String,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct Swap {
-    pub in_amount: String,
-    pub out_amount: String,
-    pub min_out_amount: String,
+    pub in_amount: U256,
+    pub out_amount: U256,
+    pub min_out_amount: U256,
 }
\ No newline at end of file
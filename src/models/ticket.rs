@@ -1,79 +1,119 @@
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
 
-use crate::models::gasless::BaseResponse;
+use crate::{models::gasless::BaseResponse, types::{Bytes, Slippage, Timestamp}, Address, Chain};
 
 
 
 
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct SubmitTicketParams {
     pub hash: String,
-    pub chain: String,
+    pub chain: Chain,
     pub version: String,
     pub question: String,
-    pub account: String,
+    pub account: Address,
     pub quote: Quote,
     pub transaction: TransactionIn,
     pub error: ErrorIn,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+params_builder! {
+    SubmitTicketParams => SubmitTicketParamsBuilder {
+        required {
+            hash: String,
+            chain: Chain,
+            question: String,
+            account: Address,
+            quote: Quote,
+            transaction: TransactionIn,
+            error: ErrorIn,
+        }
+        defaults {
+            version: String = "v4".to_string(),
+        }
+        optional {}
+    }
+}
+
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorIn {
     pub code: i32,
     pub error: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TransactionIn {
-    pub from: String,
-    pub to: String,
+    pub from: Address,
+    pub to: Address,
     pub value: String,
-    pub data: String,
+    pub data: Bytes,
     pub gas_price: String,
     pub gas_limit: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Quote {
     pub quote_type: String,
     pub in_token_symbol: String,
-    pub in_token_address: String,
+    pub in_token_address: Address,
     pub out_token_symbol: String,
-    pub out_token_address: String,
+    pub out_token_address: Address,
     pub amount_all: i32,
     pub amount: String,
     pub gas_price: String,
-    pub slippage: i32,
+    pub slippage: Slippage,
     pub referrer: Option<String>,
     #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, i32>>")]
     #[serde(rename = "disabledDexIds", skip_serializing_if = "Option::is_none")]
     pub disabled_dex_ids: Option<Vec<i32>>,
 }
 
+params_builder! {
+    Quote => QuoteBuilder {
+        required {
+            quote_type: String,
+            in_token_symbol: String,
+            in_token_address: Address,
+            out_token_symbol: String,
+            out_token_address: Address,
+            amount: String,
+            gas_price: String,
+        }
+        defaults {
+            amount_all: i32 = 0,
+            slippage: Slippage = Slippage::DEFAULT,
+        }
+        optional {
+            referrer: String,
+            disabled_dex_ids: Vec<i32>,
+        }
+    }
+}
+
 pub type SubmitTicketResponse = BaseResponse<SubmitTicketData>;
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct SubmitTicketData {
     pub ticket: String,
 }
 
 pub type GetTicketResponse = BaseResponse<GetTicketData>;
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct GetTicketData {
     pub hash: String,
     pub remark: String,
@@ -81,13 +121,13 @@ pub struct GetTicketData {
     pub question: String,
     pub answer: String,
     pub params: TicketParams,
-    pub account: String,
-    pub created_at: String,
+    pub account: Address,
+    pub created_at: Timestamp,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde_as]
 pub struct TicketParams {
     pub quote: Quote,
 }
\ No newline at end of file
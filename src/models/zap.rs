@@ -1,14 +1,14 @@
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::models::gasless::BaseResponse;
+use crate::{models::gasless::BaseResponse, types::{unix_seconds, Bytes, Percent, Slippage, Timestamp, U256}, Address};
 
 
 
 
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RouteParams {
     pub dex: String,
@@ -16,35 +16,60 @@ pub struct RouteParams {
     pub position_tick_upper: f64,
     pub position_tick_lower: f64,
     pub tokens: Vec<TokenParam>,
-    pub slippage: String,
-    pub referrer: Option<String>,
-    pub referrer_fee: Option<String>,
+    pub slippage: Slippage,
+    pub referrer: Option<Address>,
+    pub referrer_fee: Option<Percent>,
+}
+
+params_builder! {
+    RouteParams => RouteParamsBuilder {
+        required {
+            dex: String,
+            pool: String,
+            position_tick_upper: f64,
+            position_tick_lower: f64,
+            tokens: Vec<TokenParam>,
+        }
+        defaults {
+            slippage: Slippage = Slippage::DEFAULT,
+        }
+        optional {
+            referrer: Address,
+            referrer_fee: Percent,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenParam {
-    pub token: String,
-    pub amount: String,
+    pub token: Address,
+    pub amount: U256,
+}
+
+impl TokenParam {
+    pub fn new(token: Address, amount: impl Into<U256>) -> Self {
+        Self { token, amount: amount.into() }
+    }
 }
 
 
 pub type RouteResponse = BaseResponse<RouteData>;
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RouteData {
     pub chain_id: String,
     pub pool_detail: Pool,
     pub zap_details: ZapDetails,
     pub route: String,
-    pub route_address: String,
+    pub route_address: Address,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Pool {
     pub pool_id: String,
@@ -53,19 +78,19 @@ pub struct Pool {
     pub token1: Token,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Token {
     pub symbol: String,
     pub name: String,
-    pub address: String,
+    pub address: Address,
     pub decimals: u8,
     pub price: f64,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZapDetails {
     pub initial_amount_usd: f64,
@@ -75,8 +100,8 @@ pub struct ZapDetails {
 }
 
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZapAction {
     pub r#type: String,
@@ -91,18 +116,18 @@ pub enum ZapActionData {
 } 
 
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProtocalFee {
-    pub address: String,
+    pub address: Address,
     pub amount: Token,
     pub amount_usd: String,
     pub zap_fee_rate: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AggregatorSwap {
     pub token_in: ActionTokenParam,
@@ -110,17 +135,17 @@ pub struct AggregatorSwap {
     pub swap_impact: f64,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionTokenParam {
-    pub address: String,
-    pub amount: String,
+    pub address: Address,
+    pub amount: U256,
     pub amoutn_usd: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddLiquidity {
     pub token0: ActionTokenParam,
@@ -128,33 +153,54 @@ pub struct AddLiquidity {
     pub liquidity: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildRouteParams {
     pub route: String,
-    pub deadline: String,
-    pub account: String,
+    #[serde(with = "unix_seconds")]
+    pub deadline: Timestamp,
+    pub account: Address,
     pub permits: Vec<Permit>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+params_builder! {
+    BuildRouteParams => BuildRouteParamsBuilder {
+        required {
+            route: String,
+            deadline: Timestamp,
+            account: Address,
+        }
+        defaults {
+            permits: Vec<Permit> = Vec::new(),
+        }
+        optional {}
+    }
+}
+
 #[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Permit {
-    pub token: String,
-    pub permit: String,
+    pub token: Address,
+    pub permit: Bytes,
+}
+
+impl Permit {
+    pub fn new(token: Address, permit: Bytes) -> Self {
+        Self { token, permit }
+    }
 }
 
 
 pub type BuildRouteResponse = BaseResponse<BuildRouteData>;
 
-#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildRouteData {
     pub zap_details: ZapDetails,
-    pub to: String,
-    pub value: String,
-    pub data: String,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
 }
\ No newline at end of file
@@ -0,0 +1,309 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::OpenoceanError;
+
+
+
+/// A request budget: `requests` per `per`, allowing bursts of up to `requests`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl Quota {
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self { requests, per }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Zero-request or zero-length quotas have no meaningful refill rate.
+    fn validate(&self) -> Result<(), OpenoceanError> {
+        if self.requests == 0 || self.per.is_zero() {
+            return Err(OpenoceanError::InvalidParams(format!(
+                "rate limit quota must allow at least one request per non-zero period, got {} per {:?}",
+                self.requests, self.per
+            )));
+        }
+        Ok(())
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        match self.per.as_secs_f64() {
+            secs if secs > 0.0 => self.requests as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+/// What to do when a request would exceed its quota.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until the quota allows the request.
+    #[default]
+    Queue,
+    /// Fail immediately with [`OpenoceanError::RateLimited`].
+    Reject,
+}
+
+/// Rate limit settings for [`OpenoceanConfig`](crate::OpenoceanConfig).
+///
+/// Endpoint patterns are matched segment by segment against the request path
+/// as a prefix; `{...}` and `*` match any single segment, so
+/// `/v4/{chain}/quote` covers `/v4/bsc/quote` and `/v4/eth/quote`.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitConfig {
+    pub global: Option<Quota>,
+    pub endpoints: Vec<(String, Quota)>,
+    pub mode: RateLimitMode,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    quota: Option<Quota>,
+    tokens: f64,
+    updated_at: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(quota: Option<Quota>, now: Instant) -> Self {
+        Self {
+            quota,
+            tokens: quota.map(|q| q.requests as f64).unwrap_or_default(),
+            updated_at: now,
+            blocked_until: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(quota) = self.quota {
+            let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * quota.refill_per_sec()).min(quota.requests as f64);
+        }
+        self.updated_at = now;
+    }
+
+    /// Time until a token is available, zero if one is available now.
+    fn wait_time(&self, now: Instant) -> Duration {
+        let blocked = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+
+        let refill = match self.quota {
+            Some(quota) if self.tokens < 1.0 => {
+                Duration::try_from_secs_f64((1.0 - self.tokens) / quota.refill_per_sec()).unwrap_or(Duration::MAX)
+            }
+            _ => Duration::ZERO,
+        };
+
+        blocked.max(refill)
+    }
+
+    fn take(&mut self) {
+        if self.quota.is_some() {
+            self.tokens -= 1.0;
+        }
+    }
+}
+
+/// Token-bucket limiter shared by every request of a client.
+///
+/// A request takes a token from the global bucket and from the first endpoint
+/// bucket whose pattern matches its path. A `429` response carrying
+/// `Retry-After` (in seconds) pauses the bucket that governs that path.
+#[derive(Debug)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    patterns: Vec<Vec<String>>,
+    // index 0 is the global bucket, index i + 1 belongs to patterns[i]
+    buckets: Mutex<Vec<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Fails with [`OpenoceanError::InvalidParams`] on a quota allowing no
+    /// requests or with a zero period.
+    pub fn new(config: &RateLimitConfig) -> Result<Self, OpenoceanError> {
+        for quota in config.global.iter().chain(config.endpoints.iter().map(|(_, q)| q)) {
+            quota.validate()?;
+        }
+
+        let now = Instant::now();
+        let mut buckets = vec![Bucket::new(config.global, now)];
+        let mut patterns = Vec::with_capacity(config.endpoints.len());
+
+        for (pattern, quota) in &config.endpoints {
+            patterns.push(segments(pattern).map(str::to_string).collect());
+            buckets.push(Bucket::new(Some(*quota), now));
+        }
+
+        Ok(Self {
+            mode: config.mode,
+            patterns,
+            buckets: Mutex::new(buckets),
+        })
+    }
+
+    fn endpoint_bucket(&self, path: &str) -> Option<usize> {
+        self.patterns
+            .iter()
+            .position(|pattern| matches_prefix(pattern, path))
+            .map(|i| i + 1)
+    }
+
+    /// Waits for (or, in [`RateLimitMode::Reject`], checks) a token for `path`.
+    pub async fn acquire(&self, path: &str) -> Result<(), OpenoceanError> {
+        let endpoint = self.endpoint_bucket(path);
+        let mut limited = false;
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let now = Instant::now();
+
+                let mut wait = Duration::ZERO;
+                for i in std::iter::once(0).chain(endpoint) {
+                    buckets[i].refill(now);
+                    wait = wait.max(buckets[i].wait_time(now));
+                }
+
+                if wait.is_zero() {
+                    for i in std::iter::once(0).chain(endpoint) {
+                        buckets[i].take();
+                    }
+                    return Ok(());
+                }
+                wait
+            };
+
+            if !limited {
+                crate::telemetry::record_rate_limited(path, "client");
+                limited = true;
+            }
+
+            match self.mode {
+                RateLimitMode::Queue => tokio::time::sleep(wait).await,
+                RateLimitMode::Reject => return Err(OpenoceanError::RateLimited { retry_after: wait }),
+            }
+        }
+    }
+
+    /// Blocks the bucket governing `path` for `retry_after`, at most
+    /// [`MAX_RETRY_AFTER`].
+    pub fn penalize(&self, path: &str, retry_after: Duration) {
+        let index = self.endpoint_bucket(path).unwrap_or(0);
+        let now = Instant::now();
+        let until = now.checked_add(retry_after.min(MAX_RETRY_AFTER)).unwrap_or(now);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = &mut buckets[index];
+        bucket.blocked_until = Some(bucket.blocked_until.map_or(until, |b| b.max(until)));
+    }
+}
+
+pub(crate) fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+pub(crate) fn matches_prefix(pattern: &[String], path: &str) -> bool {
+    let mut path = segments(path);
+    pattern.iter().all(|p| match path.next() {
+        Some(seg) => p == "*" || (p.starts_with('{') && p.ends_with('}')) || p == seg,
+        None => false,
+    })
+}
+
+/// Upper bound on a server-requested pause, so a bogus `Retry-After` cannot
+/// stall the client indefinitely.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(600);
+
+/// Parses a `Retry-After` header given in delta-seconds, clamped to
+/// [`MAX_RETRY_AFTER`].
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(|secs| Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(mode: RateLimitMode) -> RateLimiter {
+        RateLimiter::new(&RateLimitConfig {
+            global: Some(Quota::per_second(10)),
+            endpoints: vec![("/v4/{chain}/quote".to_string(), Quota::per_second(2))],
+            mode,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rejects_empty_quotas() {
+        for quota in [Quota::per_second(0), Quota::new(5, Duration::ZERO)] {
+            let config = RateLimitConfig { endpoints: vec![("/v4".to_string(), quota)], ..Default::default() };
+            assert!(matches!(RateLimiter::new(&config), Err(OpenoceanError::InvalidParams(_))));
+        }
+
+        let config = crate::OpenoceanConfig::builder().rate_limit(Quota::per_second(0)).build();
+        assert!(crate::OpenoceanClient::new(config).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_pattern_matching() {
+        let limiter = limiter(RateLimitMode::Queue);
+        assert_eq!(limiter.endpoint_bucket("/v4/bsc/quote"), Some(1));
+        assert_eq!(limiter.endpoint_bucket("/v4/eth/quote/extra"), Some(1));
+        assert_eq!(limiter.endpoint_bucket("/v4/bsc/swap"), None);
+        assert_eq!(limiter.endpoint_bucket("/v4"), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reject_when_endpoint_quota_exhausted() {
+        let limiter = limiter(RateLimitMode::Reject);
+        limiter.acquire("/v4/bsc/quote").await.unwrap();
+        limiter.acquire("/v4/eth/quote").await.unwrap();
+
+        let err = limiter.acquire("/v4/bsc/quote").await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::RateLimited { retry_after } if retry_after == Duration::from_millis(500)));
+
+        // other endpoints only draw from the global bucket
+        limiter.acquire("/v4/bsc/gasPrice").await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queue_waits_for_refill_and_retry_after() {
+        let limiter = limiter(RateLimitMode::Queue);
+        let start = Instant::now();
+
+        limiter.acquire("/v4/bsc/quote").await.unwrap();
+        limiter.acquire("/v4/bsc/quote").await.unwrap();
+        limiter.acquire("/v4/bsc/quote").await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+
+        limiter.penalize("/v4/bsc/gasPrice", Duration::from_secs(3));
+        limiter.acquire("/v4/bsc/gasPrice").await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(3500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_after_is_clamped() {
+        assert_eq!(parse_retry_after("18446744073709551615"), Some(MAX_RETRY_AFTER));
+        assert_eq!(parse_retry_after(" 2 "), Some(Duration::from_secs(2)));
+
+        let limiter = limiter(RateLimitMode::Reject);
+        limiter.penalize("/v4/bsc/gasPrice", Duration::MAX);
+        let err = limiter.acquire("/v4/bsc/gasPrice").await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::RateLimited { retry_after } if retry_after == MAX_RETRY_AFTER));
+    }
+}
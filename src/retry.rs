@@ -0,0 +1,138 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::{TransportError, TransportErrorKind};
+
+
+
+/// Retry behaviour for requests sent by [`OpenoceanClient`](crate::OpenoceanClient).
+///
+/// Only idempotent requests (GETs) are retried unless `retry_non_idempotent`
+/// is set, so POSTs such as `Gasless::swap` or `LimitOrder::create_limit_order`
+/// are never submitted twice by accident.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every further attempt.
+    pub base_delay: Duration,
+    /// Upper bound for a single backoff delay.
+    pub max_delay: Duration,
+    /// Randomize each delay within `[delay / 2, delay]`.
+    pub jitter: bool,
+    /// HTTP statuses that are worth another attempt.
+    pub retry_statuses: Vec<u16>,
+    /// Retry when the request timed out.
+    pub retry_on_timeout: bool,
+    /// Retry when the connection could not be established.
+    pub retry_on_connect: bool,
+    /// Also retry non-idempotent requests (POST).
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+            retry_statuses: vec![408, 429, 500, 502, 503, 504],
+            retry_on_timeout: true,
+            retry_on_connect: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that makes exactly one attempt.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    fn can_retry(&self, attempt: u32, idempotent: bool) -> bool {
+        attempt < self.max_attempts && (idempotent || self.retry_non_idempotent)
+    }
+
+    pub(crate) fn should_retry_status(&self, status: u16, attempt: u32, idempotent: bool) -> bool {
+        self.can_retry(attempt, idempotent) && self.retry_statuses.contains(&status)
+    }
+
+    pub(crate) fn should_retry_error(&self, err: &TransportError, attempt: u32, idempotent: bool) -> bool {
+        self.can_retry(attempt, idempotent)
+            && match err.kind {
+                TransportErrorKind::Timeout => self.retry_on_timeout,
+                TransportErrorKind::Connect => self.retry_on_connect,
+                TransportErrorKind::Other => false,
+            }
+    }
+
+    /// Backoff before the attempt following `attempt` (1-based).
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1u32 << exp)
+            .min(self.max_delay);
+
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let spread = (delay - half).as_nanos() as u64;
+        half + Duration::from_nanos(random_u64() % (spread + 1))
+    }
+}
+
+fn random_u64() -> u64 {
+    // RandomState is seeded per instance, which is enough for jitter.
+    RandomState::new().build_hasher().finish()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_in_range() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            ..RetryPolicy::default()
+        };
+
+        for _ in 0..100 {
+            let d = policy.backoff(2);
+            assert!(d >= Duration::from_millis(100) && d <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_non_idempotent_requests_are_not_retried() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry_status(503, 1, true));
+        assert!(!policy.should_retry_status(503, 1, false));
+        assert!(!policy.should_retry_status(503, 3, true));
+        assert!(!policy.should_retry_status(400, 1, true));
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use crate::{
+    models::{gasless, swap},
+    types::Percent,
+    Address,
+};
+
+/// A quote's `path` flattened into legs, hops and per-DEX shares.
+///
+/// The API describes a route as parallel legs (each taking `percentage` of
+/// the input), each leg a chain of hops, and each hop split across DEXes by
+/// `percentage` of what enters that hop. [`HopDex::input_share`] multiplies
+/// the two, giving the share of the original input amount routed through a
+/// DEX at that hop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteAnalysis {
+    pub from: RouteToken,
+    pub to: RouteToken,
+    pub legs: Vec<RouteLeg>,
+}
+
+/// A token in a route, with its symbol once resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteToken {
+    pub address: Address,
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLeg {
+    /// Share of the input amount taking this leg.
+    pub share: Percent,
+    pub hops: Vec<RouteHop>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteHop {
+    pub from: RouteToken,
+    pub to: RouteToken,
+    pub dexes: Vec<HopDex>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopDex {
+    pub dex: String,
+    pub id: String,
+    /// Share of the amount entering this hop.
+    pub hop_share: Percent,
+    /// Share of the quote's input amount.
+    pub input_share: Percent,
+    /// Pool fee, only reported by gasless quotes.
+    pub fee: Option<f64>,
+}
+
+impl RouteToken {
+    fn new(address: &Address) -> Self {
+        Self { address: address.clone(), symbol: None }
+    }
+
+    /// The symbol, or a shortened address.
+    pub fn label(&self) -> String {
+        let address = self.address.as_str();
+        let short = address.get(..6).zip(address.get(address.len().saturating_sub(4)..));
+        match (&self.symbol, short) {
+            (Some(symbol), _) => symbol.clone(),
+            (None, Some((head, tail))) if address.len() > 12 => format!("{head}…{tail}"),
+            (None, _) => address.to_string(),
+        }
+    }
+}
+
+impl RouteAnalysis {
+    /// Fills in token symbols from `(address, symbol)` pairs, e.g. a token
+    /// list. Addresses are compared case-insensitively.
+    pub fn resolve_symbols<'a, I>(&mut self, tokens: I)
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let symbols: HashMap<String, &str> = tokens
+            .into_iter()
+            .map(|(address, symbol)| (address.to_ascii_lowercase(), symbol))
+            .collect();
+
+        let resolve = |token: &mut RouteToken| {
+            if let Some(symbol) = symbols.get(&token.address.as_str().to_ascii_lowercase()) {
+                token.symbol = Some(symbol.to_string());
+            }
+        };
+
+        resolve(&mut self.from);
+        resolve(&mut self.to);
+        for hop in self.legs.iter_mut().flat_map(|leg| leg.hops.iter_mut()) {
+            resolve(&mut hop.from);
+            resolve(&mut hop.to);
+        }
+    }
+
+    /// Fills in token symbols from a token list.
+    pub fn resolve_with_token_list(&mut self, tokens: &[swap::Token]) {
+        self.resolve_symbols(tokens.iter().map(|t| (t.address.as_str(), t.symbol.as_str())));
+    }
+
+    /// Every hop of every leg, in order.
+    pub fn hops(&self) -> impl Iterator<Item = &RouteHop> {
+        self.legs.iter().flat_map(|leg| leg.hops.iter())
+    }
+
+    /// Renders the route as a Graphviz `digraph`, one node per token and one
+    /// edge per DEX and hop, labelled with its share of the input.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph route {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        let mut seen = Vec::new();
+        for token in std::iter::once(&self.from)
+            .chain(self.hops().flat_map(|h| [&h.from, &h.to]))
+            .chain(std::iter::once(&self.to))
+        {
+            let id = token.address.as_str().to_ascii_lowercase();
+            if !seen.contains(&id) {
+                let _ = writeln!(out, "    \"{}\" [label=\"{}\"];", escape(&id), escape(&token.label()));
+                seen.push(id);
+            }
+        }
+
+        for hop in self.hops() {
+            for dex in &hop.dexes {
+                let _ = writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label=\"{} {}%\"];",
+                    escape(&hop.from.address.as_str().to_ascii_lowercase()),
+                    escape(&hop.to.address.as_str().to_ascii_lowercase()),
+                    escape(&dex.dex),
+                    fmt_share(dex.input_share),
+                );
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// ASCII tree of legs, hops and DEXes.
+impl fmt::Display for RouteAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} -> {}", self.from.label(), self.to.label())?;
+
+        for (i, leg) in self.legs.iter().enumerate() {
+            let (branch, indent) = tree_prefix(i, self.legs.len(), "");
+            writeln!(f, "{branch}{}%", fmt_share(leg.share))?;
+
+            for (j, hop) in leg.hops.iter().enumerate() {
+                let (branch, indent) = tree_prefix(j, leg.hops.len(), &indent);
+                writeln!(f, "{branch}{} -> {}", hop.from.label(), hop.to.label())?;
+
+                for (k, dex) in hop.dexes.iter().enumerate() {
+                    let (branch, _) = tree_prefix(k, hop.dexes.len(), &indent);
+                    write!(f, "{branch}{} {}% ({}% of input)", dex.dex, fmt_share(dex.hop_share), fmt_share(dex.input_share))?;
+                    match dex.fee {
+                        Some(fee) => writeln!(f, ", fee {fee}")?,
+                        None => writeln!(f)?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn tree_prefix(i: usize, len: usize, indent: &str) -> (String, String) {
+    match i + 1 == len {
+        true => (format!("{indent}└── "), format!("{indent}    ")),
+        false => (format!("{indent}├── "), format!("{indent}│   ")),
+    }
+}
+
+/// Up to two decimals, without trailing zeros.
+fn fmt_share(p: Percent) -> String {
+    let s = format!("{:.2}", p.0);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// The swap and gasless models carry identical path trees, except that gasless
+// DEX entries also report a fee.
+macro_rules! impl_from_path {
+    ($module:ident, $fee:expr) => {
+        impl From<&$module::QuotePath> for RouteAnalysis {
+            fn from(path: &$module::QuotePath) -> Self {
+                let legs = path
+                    .routes
+                    .iter()
+                    .map(|route| RouteLeg {
+                        share: Percent(route.percentage),
+                        hops: route
+                            .sub_routes
+                            .iter()
+                            .map(|sub| RouteHop {
+                                from: RouteToken::new(&sub.from),
+                                to: RouteToken::new(&sub.to),
+                                dexes: sub
+                                    .dexes
+                                    .iter()
+                                    .map(|dex| HopDex {
+                                        dex: dex.dex.clone(),
+                                        id: dex.id.clone(),
+                                        hop_share: Percent(dex.percentage),
+                                        input_share: Percent(route.percentage * dex.percentage / 100.0),
+                                        fee: $fee(dex),
+                                    })
+                                    .collect(),
+                            })
+                            .collect(),
+                    })
+                    .collect();
+
+                RouteAnalysis { from: RouteToken::new(&path.from), to: RouteToken::new(&path.to), legs }
+            }
+        }
+    };
+}
+
+impl_from_path!(swap, |_: &swap::QuoteSubRouteDex| None);
+impl_from_path!(gasless, |dex: &gasless::QuoteSubRouteDex| dex.fee);
+
+impl swap::QuoteData {
+    /// The route, with the input and output symbols filled in.
+    pub fn route(&self) -> RouteAnalysis {
+        let mut route = RouteAnalysis::from(&self.path);
+        route.resolve_symbols([
+            (self.in_token.address.as_str(), self.in_token.symbol.as_str()),
+            (self.out_token.address.as_str(), self.out_token.symbol.as_str()),
+        ]);
+        route
+    }
+}
+
+impl gasless::GaslessQuoteData {
+    /// The route, with the input and output symbols filled in.
+    pub fn route(&self) -> RouteAnalysis {
+        let mut route = RouteAnalysis::from(&self.path);
+        route.resolve_symbols([
+            (self.in_token.address.as_str(), self.in_token.symbol.as_str()),
+            (self.out_token.address.as_str(), self.out_token.symbol.as_str()),
+        ]);
+        route
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDT: &str = "0x55d398326f99059ff775485246999027b3197955";
+    const USDC: &str = "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d";
+    const WBNB: &str = "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c";
+
+    fn gasless_path() -> gasless::QuotePath {
+        serde_json::from_value(serde_json::json!({
+            "from": USDT, "to": USDC, "parts": 10,
+            "routes": [
+                { "parts": 6, "percentage": 60, "subRoutes": [
+                    { "from": USDT, "to": WBNB, "parts": 10, "dexes": [
+                        { "dex": "PancakeV3", "id": "1", "parts": 6, "percentage": 60, "fee": 0.0005 },
+                        { "dex": "UniswapV3", "id": "2", "parts": 4, "percentage": 40 }
+                    ]},
+                    { "from": WBNB, "to": USDC, "parts": 10, "dexes": [
+                        { "dex": "PancakeV2", "id": "3", "parts": 10, "percentage": 100 }
+                    ]}
+                ]},
+                { "parts": 4, "percentage": 40, "subRoutes": [
+                    { "from": USDT, "to": USDC, "parts": 10, "dexes": [
+                        { "dex": "Wombat", "id": "4", "parts": 10, "percentage": 100 }
+                    ]}
+                ]}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_effective_shares_and_tree() {
+        let mut route = RouteAnalysis::from(&gasless_path());
+        route.resolve_symbols([(USDT, "USDT"), (USDC, "USDC"), (&WBNB.to_ascii_lowercase(), "WBNB")]);
+
+        let shares: Vec<_> = route.hops().flat_map(|h| &h.dexes).map(|d| (d.dex.as_str(), d.input_share.0)).collect();
+        assert_eq!(shares, [("PancakeV3", 36.0), ("UniswapV3", 24.0), ("PancakeV2", 60.0), ("Wombat", 40.0)]);
+
+        assert_eq!(
+            route.to_string(),
+            "USDT -> USDC
+├── 60%
+│   ├── USDT -> WBNB
+│   │   ├── PancakeV3 60% (36% of input), fee 0.0005
+│   │   └── UniswapV3 40% (24% of input)
+│   └── WBNB -> USDC
+│       └── PancakeV2 100% (60% of input)
+└── 40%
+    └── USDT -> USDC
+        └── Wombat 100% (40% of input)
+"
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let route = RouteAnalysis::from(&gasless_path());
+        let dot = route.to_dot();
+
+        assert!(dot.starts_with("digraph route {"));
+        assert_eq!(dot.matches("[label=\"0x").count(), 3, "{dot}");
+        assert!(dot.contains(&format!("\"{USDT}\" -> \"{}\" [label=\"PancakeV3 36%\"];", WBNB.to_ascii_lowercase())));
+        assert!(dot.contains(&format!("\"{USDT}\" -> \"{USDC}\" [label=\"Wombat 40%\"];")));
+    }
+}
@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client, Method, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    models::swap::Transaction,
+    types::{Bytes, U256},
+    Address, ApiErrorKind, Chain, HttpRequest, OpenoceanError, ReqwestTransport, Transport,
+};
+
+/// Shortest pause between receipt polls; smaller intervals are raised to it.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Settings for [`RpcClient`].
+#[derive(Clone, Debug)]
+pub struct RpcConfig {
+    /// JSON-RPC endpoint per chain.
+    pub urls: HashMap<Chain, Url>,
+    pub timeout: Duration,
+    /// Blocks, counting the one holding the transaction, before a receipt is
+    /// reported by [`RpcClient::wait_for_receipt`].
+    pub confirmations: u64,
+    /// Pause between receipt polls, never shorter than [`MIN_POLL_INTERVAL`].
+    pub poll_interval: Duration,
+    /// How long [`RpcClient::wait_for_receipt`] waits before giving up.
+    pub receipt_timeout: Duration,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            urls: HashMap::new(),
+            timeout: Duration::from_secs(30),
+            confirmations: 1,
+            poll_interval: Duration::from_secs(2),
+            receipt_timeout: Duration::from_secs(180),
+        }
+    }
+}
+
+impl RpcConfig {
+    pub fn builder() -> RpcConfigBuilder {
+        RpcConfigBuilder { config: RpcConfig::default() }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RpcConfigBuilder {
+    config: RpcConfig,
+}
+
+impl RpcConfigBuilder {
+    pub fn url(mut self, chain: Chain, url: &str) -> Self {
+        self.config.urls.insert(chain, Url::parse(url).expect("valid rpc url"));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.config.confirmations = confirmations;
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.config.poll_interval = interval.max(MIN_POLL_INTERVAL);
+        self
+    }
+
+    pub fn receipt_timeout(mut self, timeout: Duration) -> Self {
+        self.config.receipt_timeout = timeout;
+        self
+    }
+
+    pub fn build(self) -> RpcConfig {
+        self.config
+    }
+}
+
+/// The receipt of a mined transaction, from `eth_getTransactionReceipt`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+    pub transaction_hash: Bytes,
+    pub block_hash: Bytes,
+    #[serde(with = "hex_u64")]
+    pub block_number: u64,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub contract_address: Option<Address>,
+    /// `1` on success, `0` on revert; absent before Byzantium.
+    pub status: Option<U256>,
+    pub gas_used: U256,
+    pub effective_gas_price: Option<U256>,
+}
+
+impl TransactionReceipt {
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|s| !s.is_zero())
+    }
+
+    /// `gas_used * effective_gas_price` in the native token's smallest unit.
+    pub fn gas_cost(&self) -> Option<U256> {
+        self.effective_gas_price.and_then(|price| self.gas_used.checked_mul(price))
+    }
+}
+
+/// A broadcast swap: the chain receipt, joined with OpenOcean's record of it.
+#[derive(Debug)]
+pub struct SwapExecution {
+    pub chain: Chain,
+    pub hash: Bytes,
+    pub receipt: TransactionReceipt,
+    /// From [`Swap::get_transaction`](crate::Swap::get_transaction); `None`
+    /// while OpenOcean has not indexed the transaction yet.
+    pub transaction: Option<Transaction>,
+}
+
+impl SwapExecution {
+    pub fn is_success(&self) -> bool {
+        self.receipt.is_success()
+    }
+
+    pub fn explorer_url(&self) -> Option<String> {
+        self.chain.info().map(|info| info.tx_url(&self.hash.to_hex()))
+    }
+}
+
+/// A minimal EVM JSON-RPC client for broadcasting transactions and waiting
+/// for their receipts.
+///
+/// Requests go through the same [`Transport`] abstraction as
+/// [`OpenoceanClient`](crate::OpenoceanClient); clones share it.
+#[derive(Clone, Debug)]
+pub struct RpcClient {
+    inner: Arc<RpcInner>,
+}
+
+#[derive(Debug)]
+struct RpcInner {
+    config: RpcConfig,
+    transport: Arc<dyn Transport>,
+    next_id: AtomicU64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Value,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl RpcClient {
+    pub fn new(config: RpcConfig) -> Result<Self, OpenoceanError> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| OpenoceanError::Network(format!("failed to build http client: {e}")))?;
+        Ok(Self::with_transport(config, ReqwestTransport::from_client(client)))
+    }
+
+    pub fn with_transport(config: RpcConfig, transport: impl Transport + 'static) -> Self {
+        Self { inner: Arc::new(RpcInner { config, transport: Arc::new(transport), next_id: AtomicU64::new(1) }) }
+    }
+
+    pub fn config(&self) -> &RpcConfig {
+        &self.inner.config
+    }
+
+    /// Calls `method` on `chain`'s endpoint and decodes its `result`.
+    pub async fn request<T: DeserializeOwned>(&self, chain: &Chain, method: &str, params: Value) -> Result<T, OpenoceanError> {
+        let url = self
+            .inner
+            .config
+            .urls
+            .get(chain)
+            .ok_or_else(|| OpenoceanError::InvalidParams(format!("no rpc url configured for {chain}")))?;
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let body = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let request = HttpRequest { method: Method::POST, url: url.clone(), headers, body: Some(body.to_string().into_bytes()) };
+
+        let resp = self.inner.transport.send(request).await?;
+        let text = String::from_utf8_lossy(&resp.body).into_owned();
+        if !resp.is_success() {
+            return Err(OpenoceanError::Http {
+                status: resp.status,
+                content_type: resp.headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).map(str::to_string),
+                body: text,
+            });
+        }
+        let parse_error = |e: serde_json::Error| OpenoceanError::Parse { message: e.to_string(), path: method.to_string(), body: text.clone() };
+
+        let resp: RpcResponse = serde_json::from_str(&text).map_err(parse_error)?;
+        if let Some(err) = resp.error {
+            return Err(OpenoceanError::Api {
+                code: err.code,
+                kind: ApiErrorKind::classify(err.code, &err.message),
+                message: err.message,
+                endpoint: method.to_string(),
+            });
+        }
+        serde_json::from_value(resp.result).map_err(parse_error)
+    }
+
+    /// Broadcasts a signed transaction and returns its hash.
+    pub async fn send_raw_transaction(&self, chain: &Chain, raw: &Bytes) -> Result<Bytes, OpenoceanError> {
+        self.request(chain, "eth_sendRawTransaction", json!([raw])).await
+    }
+
+    pub async fn block_number(&self, chain: &Chain) -> Result<u64, OpenoceanError> {
+        let block: U256 = self.request(chain, "eth_blockNumber", json!([])).await?;
+        block
+            .to_u128()
+            .and_then(|b| u64::try_from(b).ok())
+            .ok_or_else(|| OpenoceanError::Internal(format!("block number {block} out of range")))
+    }
+
+    /// The receipt, or `None` while the transaction is pending or unknown.
+    pub async fn get_transaction_receipt(&self, chain: &Chain, hash: &Bytes) -> Result<Option<TransactionReceipt>, OpenoceanError> {
+        self.request(chain, "eth_getTransactionReceipt", json!([hash])).await
+    }
+
+    /// Polls for the receipt until it has [`RpcConfig::confirmations`]
+    /// confirmations, failing with [`OpenoceanError::ReceiptTimeout`] after
+    /// [`RpcConfig::receipt_timeout`].
+    pub async fn wait_for_receipt(&self, chain: &Chain, hash: &Bytes) -> Result<TransactionReceipt, OpenoceanError> {
+        let config = &self.inner.config;
+        let started = tokio::time::Instant::now();
+
+        loop {
+            // Re-read the receipt every round: a reorg can move or drop it.
+            if let Some(receipt) = self.get_transaction_receipt(chain, hash).await? {
+                let confirmed = match config.confirmations {
+                    0 | 1 => true,
+                    n => self.block_number(chain).await? + 1 >= receipt.block_number + n,
+                };
+                if confirmed {
+                    return Ok(receipt);
+                }
+            }
+
+            let waited = started.elapsed();
+            if waited >= config.receipt_timeout {
+                return Err(OpenoceanError::ReceiptTimeout { hash: hash.to_hex(), waited });
+            }
+            let interval = config.poll_interval.max(MIN_POLL_INTERVAL);
+            tokio::time::sleep(interval.min(config.receipt_timeout - waited)).await;
+        }
+    }
+}
+
+/// `u64` as a JSON-RPC hex quantity.
+mod hex_u64 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &u64, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&format!("{v:#x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<u64, D::Error> {
+        let s = String::deserialize(de)?;
+        let hex = s.strip_prefix("0x").ok_or_else(|| D::Error::custom(format!("expected a 0x quantity, got {s:?}")))?;
+        u64::from_str_radix(hex, 16).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::{MockTransport, OpenoceanClient, OpenoceanConfig};
+
+    const HASH: &str = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+
+    type Handler = dyn Fn(&str, &Value) -> Result<Value, (i32, &'static str)> + Send + Sync;
+
+    /// A JSON-RPC server on a local port, answering through `handler` and
+    /// recording the methods called.
+    async fn mock_rpc(handler: Box<Handler>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let (handler, recorded): (Arc<Handler>, _) = (Arc::from(handler), calls.clone());
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let (handler, recorded) = (handler.clone(), recorded.clone());
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let body = loop {
+                        let n = socket.read(&mut chunk).await.unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                        let text = String::from_utf8_lossy(&buf);
+                        if let Some(end) = text.find("\r\n\r\n") {
+                            let len = text[..end]
+                                .lines()
+                                .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                                .unwrap_or(0);
+                            if buf.len() >= end + 4 + len {
+                                break buf[end + 4..end + 4 + len].to_vec();
+                            }
+                        }
+                        if n == 0 {
+                            return;
+                        }
+                    };
+
+                    let req: Value = serde_json::from_slice(&body).unwrap();
+                    let method = req["method"].as_str().unwrap().to_string();
+                    recorded.lock().unwrap().push(method.clone());
+                    let resp = match handler(&method, &req["params"]) {
+                        Ok(result) => json!({ "jsonrpc": "2.0", "id": req["id"], "result": result }),
+                        Err((code, message)) => json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": code, "message": message } }),
+                    }
+                    .to_string();
+                    let head = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                        resp.len()
+                    );
+                    socket.write_all(head.as_bytes()).await.unwrap();
+                    socket.write_all(resp.as_bytes()).await.unwrap();
+                    socket.shutdown().await.ok();
+                });
+            }
+        });
+
+        (url, calls)
+    }
+
+    fn receipt(block: u64) -> Value {
+        json!({
+            "transactionHash": HASH,
+            "blockHash": "0x8e38b4dbf6b11fcc3b9dee84fb7986e29ca0a02cecd8977c161ff7333329681e",
+            "blockNumber": format!("{block:#x}"),
+            "from": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "to": "0x6352a56caadc4f1e25cd6c75970fa768a3304e64",
+            "contractAddress": null,
+            "status": "0x1",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "logs": []
+        })
+    }
+
+    fn rpc_client(url: &str, confirmations: u64) -> RpcClient {
+        RpcClient::new(
+            RpcConfig::builder()
+                .url(Chain::Bsc, url)
+                .confirmations(confirmations)
+                .poll_interval(MIN_POLL_INTERVAL)
+                .receipt_timeout(Duration::from_secs(5))
+                .build(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_send_and_wait_for_confirmations() {
+        let polls = AtomicU64::new(0);
+        let head = AtomicU64::new(100);
+        let (url, calls) = mock_rpc(Box::new(move |method, params| match method {
+            "eth_sendRawTransaction" => {
+                assert_eq!(params[0], "0x02f8");
+                Ok(json!(HASH))
+            }
+            // pending on the first poll, then mined in block 100
+            "eth_getTransactionReceipt" => Ok(match polls.fetch_add(1, Ordering::SeqCst) {
+                0 => Value::Null,
+                _ => receipt(100),
+            }),
+            "eth_blockNumber" => Ok(json!(format!("{:#x}", head.fetch_add(1, Ordering::SeqCst)))),
+            _ => Err((-32601, "method not found")),
+        }))
+        .await;
+        let rpc = rpc_client(&url, 3);
+
+        let hash = rpc.send_raw_transaction(&Chain::Bsc, &"0x02f8".parse().unwrap()).await.unwrap();
+        assert_eq!(hash.to_hex(), HASH);
+
+        let receipt = rpc.wait_for_receipt(&Chain::Bsc, &hash).await.unwrap();
+        assert!(receipt.is_success());
+        assert_eq!(receipt.block_number, 100);
+        assert_eq!(receipt.gas_cost(), Some(U256::from(21_000_000_000_000u64)));
+        // head 100, 101 are not deep enough; 102 gives three confirmations
+        let calls = calls.lock().unwrap().clone();
+        assert_eq!(calls.iter().filter(|m| *m == "eth_blockNumber").count(), 3);
+        assert_eq!(calls.iter().filter(|m| *m == "eth_getTransactionReceipt").count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_and_timeout() {
+        let (url, _) = mock_rpc(Box::new(|method, _| match method {
+            "eth_sendRawTransaction" => Err((-32000, "nonce too low")),
+            _ => Ok(Value::Null),
+        }))
+        .await;
+        let rpc = RpcClient::new(
+            RpcConfig::builder().url(Chain::Bsc, &url).poll_interval(MIN_POLL_INTERVAL).receipt_timeout(Duration::from_millis(50)).build(),
+        )
+        .unwrap();
+
+        let err = rpc.send_raw_transaction(&Chain::Bsc, &"0x02".parse().unwrap()).await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::Api { code: -32000, ref endpoint, .. } if endpoint == "eth_sendRawTransaction"), "{err:?}");
+
+        let err = rpc.wait_for_receipt(&Chain::Bsc, &HASH.parse().unwrap()).await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::ReceiptTimeout { ref hash, .. } if hash == HASH), "{err:?}");
+
+        assert!(matches!(rpc.block_number(&Chain::Eth).await, Err(OpenoceanError::InvalidParams(_))));
+    }
+
+    #[tokio::test]
+    async fn test_zero_poll_interval_is_raised() {
+        assert_eq!(RpcConfig::builder().poll_interval(Duration::ZERO).build().poll_interval, MIN_POLL_INTERVAL);
+
+        let (url, calls) = mock_rpc(Box::new(|_, _| Ok(Value::Null))).await;
+        let mut config = RpcConfig::builder().url(Chain::Bsc, &url).receipt_timeout(MIN_POLL_INTERVAL * 3).build();
+        config.poll_interval = Duration::ZERO;
+        let rpc = RpcClient::new(config).unwrap();
+
+        let err = rpc.wait_for_receipt(&Chain::Bsc, &HASH.parse().unwrap()).await.unwrap_err();
+        assert!(matches!(err, OpenoceanError::ReceiptTimeout { .. }), "{err:?}");
+        assert!(calls.lock().unwrap().len() <= 4, "polled {} times", calls.lock().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_joins_openocean_record() {
+        let (url, _) = mock_rpc(Box::new(|method, _| match method {
+            "eth_sendRawTransaction" => Ok(json!(HASH)),
+            _ => Ok(receipt(100)),
+        }))
+        .await;
+        let transport = Arc::new(MockTransport::new());
+        let swap = OpenoceanClient::with_transport(OpenoceanConfig::default(), transport.clone()).unwrap().swap();
+
+        // not indexed yet, then indexed, then a malformed record
+        transport.on_json(Method::GET, "/v4/bsc/getTransaction", json!({ "code": 201, "error": "transaction not found" }));
+        transport.on_json(Method::GET, "/v4/bsc/getTransaction", json!({ "code": 200, "data": {
+            "id": 1, "tx_id": null, "block_number": 100, "tx_index": 0, "address": "0x6352a56caadc4f1e25cd6c75970fa768a3304e64",
+            "tx_hash": HASH, "tx_hash_url": "", "sender": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f", "receiver": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "in_token_address": "0x55d398326f99059ff775485246999027b3197955", "in_token_symbol": "USDT",
+            "out_token_address": "0x8ac76a51cc950d9822d68b83fe1ad97b32cd580d", "out_token_symbol": "USDC",
+            "referrer": "", "in_amount": "5000000000000000000", "out_amount": "4990000000000000000", "fee": null, "referrer_fee": null,
+            "usd_valuation": 5.0, "create_at": "2025-01-01T00:00:00.000Z", "update_at": "2025-01-01T00:00:00.000Z",
+            "tx_fee": "0", "tx_fee_valuation": "0", "in_token_decimals": 18, "out_token_decimals": 18,
+            "in_amount_value": "5", "out_amount_value": "4.99", "tx_profit": "0", "tx_profit_valuation": "0", "platform": null, "status": 1
+        }}));
+        transport.on_json(Method::GET, "/v4/bsc/getTransaction", json!({ "code": 200, "data": { "id": "not a number" } }));
+
+        let execution = swap.broadcast(&rpc_client(&url, 1), Chain::Bsc, &"0x02f8".parse().unwrap()).await.unwrap();
+        assert!(execution.is_success());
+        assert!(execution.transaction.is_none());
+        assert_eq!(execution.explorer_url().unwrap(), format!("https://bscscan.com/tx/{HASH}"));
+
+        let execution = swap.track_transaction(&rpc_client(&url, 1), Chain::Bsc, &HASH.parse().unwrap()).await.unwrap();
+        assert_eq!(execution.transaction.unwrap().out_token_amount().to_units(), "4.99");
+
+        // the receipt survives a lookup that fails to parse
+        let execution = swap.track_transaction(&rpc_client(&url, 1), Chain::Bsc, &HASH.parse().unwrap()).await.unwrap();
+        assert!(execution.is_success());
+        assert!(execution.transaction.is_none());
+    }
+}
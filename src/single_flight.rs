@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::{HttpResponse, OpenoceanError};
+
+
+
+type Shared = Arc<OnceCell<Result<HttpResponse, OpenoceanError>>>;
+
+/// Coalesces identical concurrent requests into one.
+///
+/// Callers with the same key share the result of whichever request gets to
+/// run first. If that caller is cancelled, the next waiting caller runs its
+/// own request instead, so a dropped future never strands the others.
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    calls: Mutex<HashMap<String, Shared>>,
+}
+
+impl SingleFlight {
+    pub async fn run<F>(&self, key: &str, request: F) -> Result<HttpResponse, OpenoceanError>
+    where
+        F: Future<Output = Result<HttpResponse, OpenoceanError>>,
+    {
+        let cell = self
+            .calls
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        let _guard = Guard { flight: self, key, cell: &cell };
+
+        cell.get_or_init(|| request).await.clone()
+    }
+}
+
+/// Removes the call once it has a result, or once its last caller is gone.
+struct Guard<'a> {
+    flight: &'a SingleFlight,
+    key: &'a str,
+    cell: &'a Shared,
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        let mut calls = self.flight.calls.lock().unwrap();
+        if let Some(current) = calls.get(self.key) {
+            // 2 = the map's reference plus our own
+            if Arc::ptr_eq(current, self.cell) && (self.cell.initialized() || Arc::strong_count(self.cell) == 2) {
+                calls.remove(self.key);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_calls_share_one_request() {
+        let flight = SingleFlight::default();
+        let sent = AtomicUsize::new(0);
+        let request = || async {
+            sent.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(HttpResponse::new(200, "ok"))
+        };
+
+        let (a, b, c) = tokio::join!(
+            flight.run("/v4/bsc/quote?amount=1", request()),
+            flight.run("/v4/bsc/quote?amount=1", request()),
+            flight.run("/v4/bsc/quote?amount=2", request()),
+        );
+        assert!(a.is_ok() && b.is_ok() && c.is_ok());
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+        assert!(flight.calls.lock().unwrap().is_empty());
+
+        // finished calls are not reused
+        flight.run("/v4/bsc/quote?amount=1", request()).await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancelled_leader_hands_over() {
+        let flight = SingleFlight::default();
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(HttpResponse::new(200, "leader"))
+        };
+        let fast = async { Ok(HttpResponse::new(200, "follower")) };
+
+        let leader = tokio::time::timeout(Duration::from_millis(10), flight.run("key", slow));
+        let follower = async {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            flight.run("key", fast).await
+        };
+        let (leader, follower) = tokio::join!(leader, follower);
+
+        assert!(leader.is_err());
+        assert_eq!(follower.unwrap().body, b"follower");
+        assert!(flight.calls.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::Method;
+
+#[cfg(feature = "metrics")]
+use crate::Chain;
+use crate::{HttpRequest, HttpResponse, OpenoceanError};
+
+
+
+/// Span around one API request, including its retries.
+///
+/// Without the `tracing` feature this is a zero-sized no-op.
+#[cfg(feature = "tracing")]
+pub(crate) struct RequestSpan {
+    span: tracing::Span,
+    started: tokio::time::Instant,
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct RequestSpan;
+
+#[cfg(feature = "tracing")]
+impl RequestSpan {
+    pub fn new(path: &str, request: &HttpRequest) -> Self {
+        let span = tracing::debug_span!(
+            "openocean.request",
+            http.method = %request.method,
+            endpoint = path,
+            http.status = tracing::field::Empty,
+            attempts = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            response_size = tracing::field::Empty,
+            envelope_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+
+        span.in_scope(|| {
+            tracing::debug!(
+                query = request.url.query().map(redact_query).unwrap_or_default(),
+                body = request.body.as_deref().map(redact_body).unwrap_or_default(),
+                "sending request",
+            );
+        });
+
+        Self {
+            span,
+            started: tokio::time::Instant::now(),
+        }
+    }
+
+    pub fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(fut, self.span.clone())
+    }
+
+    pub fn record_response(&self, resp: &HttpResponse, attempts: u32) {
+        self.span.record("http.status", resp.status);
+        self.span.record("attempts", attempts);
+        self.span.record("response_size", resp.body.len());
+        if let Ok(status) = serde_json::from_slice::<crate::models::base::EnvelopeStatus>(&resp.body) {
+            self.span.record("envelope_code", status.code);
+        }
+    }
+
+    pub fn finish<T>(self, result: &Result<T, OpenoceanError>) {
+        self.span.record("latency_ms", self.started.elapsed().as_millis() as u64);
+        if let Err(err) = result {
+            self.span.record("error", tracing::field::display(err));
+            self.span.in_scope(|| tracing::debug!(error = %err, "request failed"));
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl RequestSpan {
+    #[inline]
+    pub fn new(_path: &str, _request: &HttpRequest) -> Self {
+        Self
+    }
+
+    #[inline]
+    pub fn instrument<F: Future>(&self, fut: F) -> F {
+        fut
+    }
+
+    #[inline]
+    pub fn record_response(&self, _resp: &HttpResponse, _attempts: u32) {}
+
+    #[inline]
+    pub fn finish<T>(self, _result: &Result<T, OpenoceanError>) {}
+}
+
+/// Emits an event for an attempt that is about to be retried.
+#[inline]
+pub(crate) fn retrying(attempt: u32, delay: Duration, reason: &dyn std::fmt::Display) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(attempt, delay_ms = delay.as_millis() as u64, reason = %reason, "retrying request");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (attempt, delay, reason);
+}
+
+
+/// Records the outcome, latency and error variant of one API call.
+/// Responses served from the cache are counted but kept out of the latency
+/// histogram.
+#[inline]
+pub(crate) fn record_request<T>(path: &str, method: &Method, result: &Result<T, OpenoceanError>, cached: bool, elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        let (chain, endpoint) = endpoint_labels(path);
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        let cache = if cached { "hit" } else { "miss" };
+
+        metrics::counter!(
+            "openocean_requests_total",
+            "chain" => chain.clone(), "endpoint" => endpoint.clone(), "method" => method.to_string(), "outcome" => outcome,
+            "cache" => cache,
+        )
+        .increment(1);
+        if !cached {
+            metrics::histogram!("openocean_request_duration_seconds", "chain" => chain.clone(), "endpoint" => endpoint.clone())
+                .record(elapsed.as_secs_f64());
+        }
+
+        if let Err(err) = result {
+            metrics::counter!(
+                "openocean_request_errors_total",
+                "chain" => chain, "endpoint" => endpoint, "error" => error_label(err),
+            )
+            .increment(1);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (path, method, result, cached, elapsed);
+}
+
+/// Counts a request that hit a rate limit, either the client's own limiter
+/// (`source = "client"`) or a `429` from the API (`source = "server"`).
+#[inline]
+pub(crate) fn record_rate_limited(path: &str, source: &'static str) {
+    #[cfg(feature = "metrics")]
+    {
+        let (chain, endpoint) = endpoint_labels(path);
+        metrics::counter!("openocean_rate_limited_total", "chain" => chain, "endpoint" => endpoint, "source" => source)
+            .increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (path, source);
+}
+
+#[inline]
+pub(crate) fn record_retry(path: &str) {
+    #[cfg(feature = "metrics")]
+    {
+        let (chain, endpoint) = endpoint_labels(path);
+        metrics::counter!("openocean_retries_total", "chain" => chain, "endpoint" => endpoint).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = path;
+}
+
+/// Splits a request path into a chain label and a low-cardinality endpoint
+/// label: `/v2/bsc/dca/address/0xabc` becomes `("bsc", "/v2/{chain}/dca/address/{id}")`
+/// and `/my-dapp/ticket` becomes `("", "/{referer}/ticket")`.
+#[cfg(feature = "metrics")]
+fn endpoint_labels(path: &str) -> (String, String) {
+    if path.strip_suffix("/ticket").is_some_and(|referer| referer.rfind('/') == Some(0)) {
+        return (String::new(), "/{referer}/ticket".to_string());
+    }
+
+    let mut chain = None;
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            if chain.is_none() && Chain::from_code(segment).is_some() {
+                chain = Some(segment.to_string());
+                "{chain}".to_string()
+            } else if segment.starts_with("0x") || segment.len() >= 32 {
+                "{id}".to_string()
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+
+    (chain.unwrap_or_default(), segments.join("/"))
+}
+
+#[cfg(feature = "metrics")]
+fn error_label(err: &OpenoceanError) -> &'static str {
+    match err {
+        OpenoceanError::Network(_) => "network",
+        OpenoceanError::Http { .. } => "http",
+        OpenoceanError::Parse { .. } => "parse",
+        OpenoceanError::Api { .. } => "api",
+        OpenoceanError::RateLimited { .. } => "rate_limited",
+        OpenoceanError::InvalidParams(_) => "invalid_params",
+        OpenoceanError::UnsupportedChain { .. } => "unsupported_chain",
+        OpenoceanError::ReceiptTimeout { .. } => "receipt_timeout",
+        OpenoceanError::Internal(_) => "internal",
+    }
+}
+
+
+/// Fields whose values never show up in spans or events: signatures,
+/// permits and raw calldata.
+#[cfg(feature = "tracing")]
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("signature")
+        || key.contains("permit")
+        || key.contains("calldata")
+        || key.contains("apikey")
+        || key == "data"
+        || key == "inputdata"
+}
+
+#[cfg(feature = "tracing")]
+const REDACTED: &str = "***";
+
+#[cfg(feature = "tracing")]
+fn redact_query(query: &str) -> String {
+    serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| {
+            let v = if is_sensitive(&k) { REDACTED } else { &v };
+            format!("{k}={v}")
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(feature = "tracing")]
+fn redact_body(body: &[u8]) -> String {
+    fn redact(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (k, v) in map.iter_mut() {
+                    if is_sensitive(k) {
+                        *v = serde_json::Value::String(REDACTED.into());
+                    } else {
+                        redact(v);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+            _ => {}
+        }
+    }
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(mut value) => {
+            redact(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("<{} bytes>", body.len()),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_endpoint_labels() {
+        assert_eq!(endpoint_labels("/v4/bsc/quote"), ("bsc".to_string(), "/v4/{chain}/quote".to_string()));
+        assert_eq!(
+            endpoint_labels("/v2/eth/dca/address/0x2FF855378Cd29f120CDF9d675E959cb5422ec5f2"),
+            ("eth".to_string(), "/v2/{chain}/dca/address/{id}".to_string())
+        );
+        assert_eq!(endpoint_labels("/v4/gasless/base/swap").1, "/v4/gasless/{chain}/swap");
+        assert_eq!(endpoint_labels("/my-dapp/ticket"), (String::new(), "/{referer}/ticket".to_string()));
+        assert_eq!(endpoint_labels("/bsc/ticket"), (String::new(), "/{referer}/ticket".to_string()));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_record_request() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let err: Result<(), _> = Err(OpenoceanError::Http { status: 503, body: String::new(), content_type: None });
+            record_request("/v4/bsc/quote", &Method::GET, &err, false, Duration::from_millis(120));
+            record_request("/v4/bsc/tokenList", &Method::GET, &Ok(()), true, Duration::ZERO);
+        });
+
+        let metrics = snapshotter.snapshot().into_vec();
+        let errors = metrics
+            .iter()
+            .find(|(key, ..)| key.key().name() == "openocean_request_errors_total")
+            .unwrap();
+        assert!(errors.0.key().labels().any(|l| l.key() == "error" && l.value() == "http"));
+        assert_eq!(errors.3, DebugValue::Counter(1));
+
+        // cache hits are counted, but stay out of the latency histogram
+        let hits = metrics
+            .iter()
+            .find(|(key, ..)| {
+                key.key().name() == "openocean_requests_total" && key.key().labels().any(|l| l.key() == "cache" && l.value() == "hit")
+            })
+            .unwrap();
+        assert!(hits.0.key().labels().any(|l| l.key() == "endpoint" && l.value() == "/v4/{chain}/tokenList"));
+        let durations: Vec<_> = metrics
+            .iter()
+            .filter(|(key, ..)| key.key().name() == "openocean_request_duration_seconds")
+            .collect();
+        assert_eq!(durations.len(), 1);
+        assert!(durations[0].0.key().labels().any(|l| l.key() == "endpoint" && l.value() == "/v4/{chain}/quote"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_sensitive_fields_are_redacted() {
+        assert_eq!(
+            redact_query("chain=bsc&data=0xdeadbeef&inAmount=1"),
+            "chain=bsc&data=***&inAmount=1"
+        );
+
+        let body = br#"{"orderHash":"0x01","signature":"0x02","permits":[{"token":"0x03","permit":"0x04"}]}"#;
+        assert_eq!(redact_body(body), r#"{"orderHash":"0x01","permits":"***","signature":"***"}"#);
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client, Method, Url};
+use thiserror::Error;
+
+use crate::{OpenoceanConfig, OpenoceanError};
+
+
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A fully built HTTP request handed to a [`Transport`].
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+/// The raw HTTP response returned by a [`Transport`].
+#[derive(Clone, Debug, Default)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// A response with a JSON body and matching `Content-Type`.
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        let mut resp = Self::new(status, body.to_string());
+        resp.headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        resp
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            self.headers.insert(name, value);
+        }
+        self
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    Timeout,
+    Connect,
+    Other,
+}
+
+/// A failure to obtain any HTTP response.
+#[derive(Clone, Debug, Error)]
+#[error("{message}")]
+pub struct TransportError {
+    pub kind: TransportErrorKind,
+    pub message: String,
+}
+
+impl TransportError {
+    pub fn new(kind: TransportErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if err.is_connect() {
+            TransportErrorKind::Connect
+        } else {
+            TransportErrorKind::Other
+        };
+        Self::new(kind, err.to_string())
+    }
+}
+
+impl From<TransportError> for OpenoceanError {
+    fn from(err: TransportError) -> Self {
+        match err.kind {
+            TransportErrorKind::Timeout => OpenoceanError::Network("timeout".into()),
+            _ => OpenoceanError::Network(err.message),
+        }
+    }
+}
+
+/// Sends HTTP requests on behalf of [`OpenoceanClient`](crate::OpenoceanClient).
+///
+/// Retries, rate limiting and response parsing all happen above the transport,
+/// so an implementation only has to move bytes.
+pub trait Transport: Send + Sync + fmt::Debug {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        (**self).send(request)
+    }
+}
+
+
+/// The default transport, backed by `reqwest`.
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(config: &OpenoceanConfig) -> Result<Self, OpenoceanError> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| OpenoceanError::Network(format!("failed to build http client: {e}")))?;
+
+        Ok(Self { client })
+    }
+
+    /// Uses an existing `reqwest::Client`, e.g. one with custom TLS or proxies.
+    pub fn from_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let resp = builder.send().await?;
+            let status = resp.status().as_u16();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?.to_vec();
+
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
+
+
+/// In-memory transport returning canned responses keyed by method and path.
+///
+/// Responses registered for the same route are served in order, the last one
+/// being repeated. Unknown routes answer `404`. Every request is recorded.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Mutex<HashMap<(Method, String), VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+    latency: Duration,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delays every response by `latency`.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Queues `response` for requests to `path` (without query string).
+    pub fn on(&self, method: Method, path: &str, response: HttpResponse) -> &Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .entry((method, path.to_string()))
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    pub fn on_json(&self, method: Method, path: &str, body: serde_json::Value) -> &Self {
+        self.on(method, path, HttpResponse::json(200, &body))
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        let key = (request.method.clone(), request.url.path().to_string());
+        let mut routes = self.routes.lock().unwrap();
+
+        match routes.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => HttpResponse::new(404, format!("no mock for {} {}", key.0, key.1)),
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, TransportError>> {
+        let response = self.respond(&request);
+        self.requests.lock().unwrap().push(request);
+        let latency = self.latency;
+        Box::pin(async move {
+            if !latency.is_zero() {
+                tokio::time::sleep(latency).await;
+            }
+            Ok(response)
+        })
+    }
+}
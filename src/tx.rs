@@ -0,0 +1,265 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{
+    models::{swap::{GasPriceTierInt, SwapQuoteData}, sweep_swap::MultiSwapQuoteResponse, zap::BuildRouteData},
+    types::{Bytes, Percent, U256},
+    Address, Chain, ChainFamily, OpenoceanError,
+};
+
+/// Fee fields of an EVM transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxFees {
+    Legacy { gas_price: U256 },
+    Eip1559 { max_fee_per_gas: U256, max_priority_fee_per_gas: U256 },
+}
+
+impl TxFees {
+    pub fn legacy(tier: &GasPriceTierInt) -> Self {
+        TxFees::Legacy { gas_price: tier.legacy_gas_price }
+    }
+
+    pub fn eip1559(tier: &GasPriceTierInt) -> Self {
+        TxFees::Eip1559 {
+            max_fee_per_gas: tier.max_fee_per_gas,
+            max_priority_fee_per_gas: tier.max_priority_fee_per_gas,
+        }
+    }
+}
+
+/// An unsigned EVM transaction built from a swap, zap or sweep response.
+///
+/// Serializes as a JSON-RPC transaction object (`eth_call`,
+/// `eth_estimateGas`, `eth_sendTransaction`) with hex quantities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionRequest {
+    pub chain_id: u64,
+    pub from: Option<Address>,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub gas_limit: Option<U256>,
+    pub fees: Option<TxFees>,
+    pub nonce: Option<u64>,
+}
+
+impl TransactionRequest {
+    /// From [`Swap::swap_quote`](crate::Swap::swap_quote), with the quote's
+    /// `estimated_gas` as gas limit and its `gas_price` as legacy fee.
+    pub fn from_swap_quote(chain: &Chain, quote: &SwapQuoteData) -> Result<Self, OpenoceanError> {
+        let reported = u64::try_from(quote.chain_id)
+            .map_err(|_| OpenoceanError::Internal(format!("response has an invalid chain id {}", quote.chain_id)))?;
+        Ok(Self {
+            chain_id: checked_chain_id(chain, Some(reported))?,
+            from: Some(quote.from.clone()),
+            to: quote.to.clone(),
+            value: quote.value,
+            data: quote.data.clone(),
+            gas_limit: Some(quote.estimated_gas),
+            fees: Some(TxFees::Legacy { gas_price: quote.gas_price }),
+            nonce: None,
+        })
+    }
+
+    /// From [`Zap::build_route`](crate::Zap::build_route). The response has
+    /// no sender, gas or fees; set them with the `with_*` methods.
+    pub fn from_build_route(chain: &Chain, route: &BuildRouteData) -> Result<Self, OpenoceanError> {
+        Ok(Self {
+            chain_id: checked_chain_id(chain, None)?,
+            from: None,
+            to: Address::for_chain(chain, route.to.as_str())?,
+            value: route.value,
+            data: route.data.clone(),
+            gas_limit: None,
+            fees: None,
+            nonce: None,
+        })
+    }
+
+    /// From [`SweepSwap::multi_swap_quote`](crate::SweepSwap::multi_swap_quote),
+    /// with its `gas_price` as legacy fee.
+    pub fn from_multi_swap_quote(chain: &Chain, quote: &MultiSwapQuoteResponse) -> Result<Self, OpenoceanError> {
+        Ok(Self {
+            chain_id: checked_chain_id(chain, Some(quote.chain_id))?,
+            from: Some(Address::for_chain(chain, quote.from.as_str())?),
+            to: Address::for_chain(chain, quote.to.as_str())?,
+            value: quote.value,
+            data: quote.data.clone(),
+            gas_limit: None,
+            fees: Some(TxFees::Legacy { gas_price: quote.gas_price }),
+            nonce: None,
+        })
+    }
+
+    pub fn with_from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: impl Into<U256>) -> Self {
+        self.gas_limit = Some(gas_limit.into());
+        self
+    }
+
+    /// Raises the gas limit by `buffer`, e.g. `Percent(20.0)` for 20% headroom
+    /// over the estimate. No-op without a gas limit.
+    pub fn with_gas_buffer(mut self, buffer: impl Into<Percent>) -> Self {
+        let bps = U256::from(10_000 + buffer.into().to_bps().0 as u64);
+        self.gas_limit = self
+            .gas_limit
+            .map(|gas| gas.checked_mul(bps).and_then(|g| g.checked_div(U256::from(10_000u64))).unwrap_or(U256::MAX));
+        self
+    }
+
+    pub fn with_fees(mut self, fees: TxFees) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Legacy `gasPrice` from a gas price tier, e.g.
+    /// `gas.tier(GasTier::Fast)` from [`Swap::get_gas_price`](crate::Swap::get_gas_price).
+    pub fn with_legacy_fees(self, tier: &GasPriceTierInt) -> Self {
+        self.with_fees(TxFees::legacy(tier))
+    }
+
+    /// EIP-1559 `maxFeePerGas` / `maxPriorityFeePerGas` from a gas price tier.
+    pub fn with_eip1559_fees(self, tier: &GasPriceTierInt) -> Self {
+        self.with_fees(TxFees::eip1559(tier))
+    }
+
+    pub fn is_eip1559(&self) -> bool {
+        matches!(self.fees, Some(TxFees::Eip1559 { .. }))
+    }
+}
+
+/// The chain's EIP-155 id, checked against the id reported by the response.
+fn checked_chain_id(chain: &Chain, reported: Option<u64>) -> Result<u64, OpenoceanError> {
+    if chain.info().is_some_and(|info| info.family != ChainFamily::Evm) {
+        return Err(OpenoceanError::InvalidParams(format!("{chain} is not an EVM chain")));
+    }
+
+    match (chain.chain_id(), reported) {
+        (Some(expected), Some(reported)) if expected != reported => Err(OpenoceanError::InvalidParams(format!(
+            "response is for chain id {reported}, expected {expected} ({chain})"
+        ))),
+        (Some(id), _) | (None, Some(id)) => Ok(id),
+        (None, None) => Err(OpenoceanError::InvalidParams(format!("no chain id known for {chain}"))),
+    }
+}
+
+fn quantity(v: U256) -> String {
+    format!("0x{v:x}")
+}
+
+impl Serialize for TransactionRequest {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut map = ser.serialize_map(None)?;
+        map.serialize_entry("chainId", &quantity(self.chain_id.into()))?;
+        if let Some(from) = &self.from {
+            map.serialize_entry("from", from)?;
+        }
+        map.serialize_entry("to", &self.to)?;
+        map.serialize_entry("value", &quantity(self.value))?;
+        map.serialize_entry("data", &self.data)?;
+        if let Some(gas) = self.gas_limit {
+            map.serialize_entry("gas", &quantity(gas))?;
+        }
+        match self.fees {
+            Some(TxFees::Legacy { gas_price }) => {
+                map.serialize_entry("type", "0x0")?;
+                map.serialize_entry("gasPrice", &quantity(gas_price))?;
+            }
+            Some(TxFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas }) => {
+                map.serialize_entry("type", "0x2")?;
+                map.serialize_entry("maxFeePerGas", &quantity(max_fee_per_gas))?;
+                map.serialize_entry("maxPriorityFeePerGas", &quantity(max_priority_fee_per_gas))?;
+            }
+            None => {}
+        }
+        if let Some(nonce) = self.nonce {
+            map.serialize_entry("nonce", &quantity(nonce.into()))?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::swap::{GasPriceData, GasTier};
+
+    use super::*;
+
+    const ROUTER: &str = "0x6352a56caadC4F1E25CD6c75970Fa768A3304e64";
+
+    fn build_route() -> BuildRouteData {
+        serde_json::from_value(serde_json::json!({
+            "zapDetails": { "initialAmountUsd": 1.0, "actions": [], "addedLiquidityUsd": 1.0, "zapImpact": 0.0 },
+            "to": ROUTER, "value": "1000", "data": "0x12345678"
+        }))
+        .unwrap()
+    }
+
+    fn gas() -> GasPriceData {
+        let tier = |base: u64| serde_json::json!({
+            "legacyGasPrice": base, "maxPriorityFeePerGas": base / 10, "maxFeePerGas": base * 2, "waitTimeEstimate": 15000
+        });
+        serde_json::from_value(serde_json::json!({
+            "base": 1000000000u64, "low": tier(1_000_000_000), "standard": tier(2_000_000_000),
+            "fast": tier(3_000_000_000), "instant": tier(4_000_000_000)
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fees_gas_buffer_and_rpc_json() {
+        let gas = gas();
+        let tx = TransactionRequest::from_build_route(&Chain::Base, &build_route())
+            .unwrap()
+            .with_gas_limit(200_000u64)
+            .with_gas_buffer(Percent(25.0))
+            .with_eip1559_fees(gas.evm().unwrap().tier(GasTier::Fast))
+            .with_nonce(7);
+
+        assert_eq!(tx.chain_id, 8453);
+        assert_eq!(tx.gas_limit, Some(U256::from(250_000u64)));
+        assert_eq!(
+            serde_json::to_value(&tx).unwrap(),
+            serde_json::json!({
+                "chainId": "0x2105", "to": ROUTER, "value": "0x3e8", "data": "0x12345678", "gas": "0x3d090",
+                "type": "0x2", "maxFeePerGas": "0x165a0bc00", "maxPriorityFeePerGas": "0x11e1a300", "nonce": "0x7"
+            })
+        );
+
+        let legacy = tx.with_legacy_fees(gas.evm().unwrap().tier(GasTier::Standard));
+        assert_eq!(legacy.fees, Some(TxFees::Legacy { gas_price: U256::from(2_000_000_000u64) }));
+    }
+
+    #[test]
+    fn test_from_multi_swap_quote() {
+        let token = serde_json::json!({ "address": ROUTER, "decimals": 18, "symbol": "T", "name": "T" });
+        for chain_id in [serde_json::json!("56"), serde_json::json!(56)] {
+            let quote: MultiSwapQuoteResponse = serde_json::from_value(serde_json::json!({
+                "inToken": [token], "outToken": token, "from": ROUTER, "to": ROUTER, "swap": [],
+                "gasPrice": "3000000000", "chainId": chain_id, "value": "0", "data": "0x12345678"
+            }))
+            .unwrap();
+            let tx = TransactionRequest::from_multi_swap_quote(&Chain::Bsc, &quote).unwrap();
+            assert_eq!((tx.chain_id, tx.fees), (56, Some(TxFees::Legacy { gas_price: U256::from(3_000_000_000u64) })));
+        }
+    }
+
+    #[test]
+    fn test_chain_id_checks() {
+        assert!(matches!(
+            TransactionRequest::from_build_route(&Chain::Solana, &build_route()),
+            Err(OpenoceanError::InvalidParams(_))
+        ));
+        assert!(matches!(checked_chain_id(&Chain::Bsc, Some(1)), Err(OpenoceanError::InvalidParams(_))));
+        assert_eq!(checked_chain_id(&Chain::Bsc, Some(56)).unwrap(), 56);
+        assert_eq!(checked_chain_id(&"777777".parse().unwrap(), None).unwrap(), 777777);
+    }
+}
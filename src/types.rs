@@ -1,5 +1,8 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::OpenoceanError;
 
 
 
@@ -77,4 +80,1052 @@ impl fmt::Display for F64 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
-}
\ No newline at end of file
+}
+
+
+
+mod inner {
+    #![allow(clippy::all)]
+    uint::construct_uint! {
+        pub(super) struct Inner(4);
+    }
+}
+use inner::Inner;
+
+/// 256-bit unsigned integer for raw (base unit) token amounts.
+///
+/// Deserializes from decimal or `0x` hex strings, integers, and integral
+/// numbers in scientific notation (`"1.0106346115864016e21"`); serializes as
+/// a decimal string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(Inner);
+
+impl U256 {
+    pub const ZERO: U256 = U256(Inner([0; 4]));
+    pub const MAX: U256 = U256(Inner([u64::MAX; 4]));
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// `10^exp`, or `None` past `U256::MAX`.
+    pub fn exp10(exp: u32) -> Option<U256> {
+        (exp <= 77).then(|| U256(Inner::exp10(exp as usize)))
+    }
+
+    pub fn checked_add(self, other: U256) -> Option<U256> {
+        self.0.checked_add(other.0).map(U256)
+    }
+
+    pub fn checked_sub(self, other: U256) -> Option<U256> {
+        self.0.checked_sub(other.0).map(U256)
+    }
+
+    pub fn checked_mul(self, other: U256) -> Option<U256> {
+        self.0.checked_mul(other.0).map(U256)
+    }
+
+    pub fn checked_div(self, other: U256) -> Option<U256> {
+        self.0.checked_div(other.0).map(U256)
+    }
+
+    pub fn saturating_sub(self, other: U256) -> U256 {
+        U256(self.0.saturating_sub(other.0))
+    }
+
+    /// The value as `u128`, or `None` if it doesn't fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        (self.0.bits() <= 128).then(|| self.0.as_u128())
+    }
+
+    /// Nearest `f64`; precision is lost past 2^53.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::INFINITY)
+    }
+
+    /// Big-endian bytes with leading zeros stripped (empty for zero).
+    pub fn to_be_bytes_trimmed(&self) -> Vec<u8> {
+        let buf = self.0.to_big_endian();
+        let start = buf.iter().position(|b| *b != 0).unwrap_or(32);
+        buf[start..].to_vec()
+    }
+}
+
+fn parse_u256(s: &str) -> Result<U256, String> {
+    let s = s.trim();
+
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return Inner::from_str_radix(hex, 16).map(U256).map_err(|e| e.to_string());
+    }
+
+    let (mantissa, exp) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i32>().map_err(|e| format!("bad exponent: {e}"))?),
+        None => (s, 0),
+    };
+    let mantissa = mantissa.strip_prefix('+').unwrap_or(mantissa);
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int.is_empty() && frac.is_empty() || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err("not an unsigned number".into());
+    }
+
+    let mut digits = format!("{int}{frac}");
+    let shift = exp.saturating_sub(frac.len() as i32);
+    if shift >= 0 {
+        if shift > 80 {
+            return match digits.bytes().all(|b| b == b'0') {
+                true => Ok(U256::ZERO),
+                false => Err("overflows U256".into()),
+            };
+        }
+        digits.extend(std::iter::repeat_n('0', shift as usize));
+    } else {
+        let keep = digits.len().saturating_sub(shift.unsigned_abs() as usize);
+        if digits[keep..].bytes().any(|b| b != b'0') {
+            return Err("not an integer".into());
+        }
+        digits.truncate(keep);
+    }
+
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    Inner::from_dec_str(digits).map(U256).map_err(|_| "overflows U256".into())
+}
+
+impl std::str::FromStr for U256 {
+    type Err = OpenoceanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_u256(s).map_err(|e| OpenoceanError::InvalidParams(format!("invalid amount {s:?}: {e}")))
+    }
+}
+
+impl TryFrom<&str> for U256 {
+    type Error = OpenoceanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for U256 {
+    type Error = OpenoceanError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(v: u64) -> Self {
+        U256(Inner::from(v))
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(v: u128) -> Self {
+        U256(Inner::from(v))
+    }
+}
+
+impl From<U128> for U256 {
+    fn from(v: U128) -> Self {
+        U256::from(v.0)
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for U256 {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ser.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+        impl<'de> serde::de::Visitor<'de> for V {
+            type Value = U256;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("unsigned integer, numeric string or hex string")
+            }
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> { Ok(U256::from(v)) }
+            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> { Ok(U256::from(v)) }
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where E: serde::de::Error {
+                if v < 0 { return Err(E::custom("negative not allowed")); }
+                Ok(U256::from(v as u64))
+            }
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where E: serde::de::Error {
+                if !v.is_finite() || v < 0.0 { return Err(E::custom("negative not allowed")); }
+                // shortest repr, so 1.0106346115864016e21 keeps its decimal digits
+                parse_u256(&format!("{v:e}")).map_err(E::custom)
+            }
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where E: serde::de::Error {
+                parse_u256(s).map_err(E::custom)
+            }
+        }
+        de.deserialize_any(V)
+    }
+}
+
+
+
+
+/// A raw amount together with its token's decimals.
+///
+/// Converts between human units (`"1.5"`) and base units
+/// (`1500000000000000000` at 18 decimals) without going through `f64`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+    pub raw: U256,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: impl Into<U256>, decimals: u8) -> Self {
+        Self { raw: raw.into(), decimals }
+    }
+
+    /// Parses a human-unit amount such as `"1.5"`. Fails on more fractional
+    /// digits than `decimals` allows, rather than rounding.
+    pub fn from_units(units: &str, decimals: u8) -> Result<Self, OpenoceanError> {
+        let err = |msg: &str| OpenoceanError::InvalidParams(format!("invalid amount {units:?}: {msg}"));
+
+        let (int, frac) = units.trim().split_once('.').unwrap_or((units.trim(), ""));
+        if int.is_empty() && frac.is_empty() || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(err("not an unsigned decimal"));
+        }
+        let frac = frac.trim_end_matches('0');
+        if frac.len() > decimals as usize {
+            return Err(err(&format!("more than {decimals} decimals")));
+        }
+
+        let mut digits = format!("{int}{frac}");
+        digits.extend(std::iter::repeat_n('0', decimals as usize - frac.len()));
+        let raw = parse_u256(&digits).map_err(|e| err(&e))?;
+        Ok(Self { raw, decimals })
+    }
+
+    /// The exact amount in human units, without trailing zeros.
+    pub fn to_units(&self) -> String {
+        let digits = self.raw.to_string();
+        let decimals = self.decimals as usize;
+        if decimals == 0 {
+            return digits;
+        }
+
+        let padded = format!("{digits:0>width$}", width = decimals + 1);
+        let (int, frac) = padded.split_at(padded.len() - decimals);
+        match frac.trim_end_matches('0') {
+            "" => int.to_string(),
+            frac => format!("{int}.{frac}"),
+        }
+    }
+
+    /// Nearest `f64` in human units; for display only.
+    pub fn to_f64(&self) -> f64 {
+        self.to_units().parse().unwrap_or(f64::INFINITY)
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_units())
+    }
+}
+
+impl From<TokenAmount> for U256 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.raw
+    }
+}
+
+
+/// A percentage in percent units: `Percent(1.0)` is 1%.
+///
+/// Parses the API's formats (`"0.01%"`, `"-0.5"`, numbers) and serializes as
+/// a plain number, so it reads the same in query strings and JSON bodies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Percent(pub f64);
+
+impl Percent {
+    pub const ZERO: Percent = Percent(0.0);
+
+    /// From a fraction, e.g. `0.005` for 0.5%.
+    pub fn from_fraction(fraction: f64) -> Self {
+        Percent(fraction * 100.0)
+    }
+
+    pub fn fraction(self) -> f64 {
+        self.0 / 100.0
+    }
+
+    /// Rounded to the nearest basis point; negative values clamp to zero.
+    pub fn to_bps(self) -> BasisPoints {
+        BasisPoints((self.0 * 100.0).round().max(0.0) as u32)
+    }
+}
+
+fn parse_percent(s: &str) -> Result<Percent, OpenoceanError> {
+    let trimmed = s.trim();
+    let number = trimmed.strip_suffix('%').unwrap_or(trimmed).trim_end();
+    match number.parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(Percent(v)),
+        _ => Err(OpenoceanError::InvalidParams(format!("invalid percentage {s:?}"))),
+    }
+}
+
+impl std::str::FromStr for Percent {
+    type Err = OpenoceanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_percent(s)
+    }
+}
+
+impl TryFrom<&str> for Percent {
+    type Error = OpenoceanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        parse_percent(s)
+    }
+}
+
+impl TryFrom<String> for Percent {
+    type Error = OpenoceanError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        parse_percent(&s)
+    }
+}
+
+impl From<f64> for Percent {
+    fn from(v: f64) -> Self {
+        Percent(v)
+    }
+}
+
+impl From<i32> for Percent {
+    fn from(v: i32) -> Self {
+        Percent(v as f64)
+    }
+}
+
+impl From<BasisPoints> for Percent {
+    fn from(bps: BasisPoints) -> Self {
+        Percent(bps.0 as f64 / 100.0)
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+impl Serialize for Percent {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // whole numbers as integers, so `1` doesn't become `1.0` in a query string
+        if self.0.fract() == 0.0 && self.0.abs() < i64::MAX as f64 {
+            ser.serialize_i64(self.0 as i64)
+        } else {
+            ser.serialize_f64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Percent {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+        impl<'de> serde::de::Visitor<'de> for V {
+            type Value = Percent;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("percentage as number or string")
+            }
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> { Ok(Percent(v)) }
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> { Ok(Percent(v as f64)) }
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> { Ok(Percent(v as f64)) }
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where E: serde::de::Error {
+                parse_percent(s).map_err(E::custom)
+            }
+        }
+        de.deserialize_any(V)
+    }
+}
+
+
+
+
+/// Hundredths of a percent: `BasisPoints(50)` is 0.5%.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BasisPoints(pub u32);
+
+impl BasisPoints {
+    pub fn to_percent(self) -> Percent {
+        Percent::from(self)
+    }
+}
+
+impl fmt::Display for BasisPoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bps", self.0)
+    }
+}
+
+
+
+
+/// Slippage tolerance, checked against the range the API accepts
+/// (0.05% to 50%) when built.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Slippage(Percent);
+
+impl Slippage {
+    pub const MIN: Percent = Percent(0.05);
+    pub const MAX: Percent = Percent(50.0);
+    /// 1%, the API's default.
+    pub const DEFAULT: Slippage = Slippage(Percent(1.0));
+
+    pub fn new(percent: impl Into<Percent>) -> Result<Self, OpenoceanError> {
+        let percent = percent.into();
+        if !(Self::MIN..=Self::MAX).contains(&percent) {
+            return Err(OpenoceanError::InvalidParams(format!(
+                "slippage {percent} out of range {}..={}", Self::MIN, Self::MAX,
+            )));
+        }
+        Ok(Slippage(percent))
+    }
+
+    pub fn percent(self) -> Percent {
+        self.0
+    }
+}
+
+impl Default for Slippage {
+    fn default() -> Self {
+        Slippage::DEFAULT
+    }
+}
+
+impl TryFrom<Percent> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(p: Percent) -> Result<Self, Self::Error> {
+        Slippage::new(p)
+    }
+}
+
+impl TryFrom<BasisPoints> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(bps: BasisPoints) -> Result<Self, Self::Error> {
+        Slippage::new(bps)
+    }
+}
+
+impl TryFrom<f64> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(v: f64) -> Result<Self, Self::Error> {
+        Slippage::new(v)
+    }
+}
+
+impl TryFrom<i32> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        Slippage::new(v)
+    }
+}
+
+impl TryFrom<&str> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Slippage::new(parse_percent(s)?)
+    }
+}
+
+impl TryFrom<String> for Slippage {
+    type Error = OpenoceanError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Slippage::try_from(s.as_str())
+    }
+}
+
+impl fmt::Display for Slippage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+
+/// Hex-encoded bytes: calldata, permits and signatures.
+///
+/// Deserializes from `0x`-prefixed hex (an empty string is read as no bytes)
+/// and serializes back as lowercase `0x` hex.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(Vec<u8>);
+
+impl Bytes {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Bytes(bytes.into())
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// The 4-byte function selector of a calldata payload.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.0.get(..4).map(|s| [s[0], s[1], s[2], s[3]])
+    }
+
+    pub fn to_hex(&self) -> String {
+        let mut out = String::with_capacity(2 + self.0.len() * 2);
+        out.push_str("0x");
+        for b in &self.0 {
+            out.push_str(&format!("{b:02x}"));
+        }
+        out
+    }
+}
+
+fn parse_hex_bytes(s: &str) -> Result<Bytes, OpenoceanError> {
+    let err = |msg: &str| OpenoceanError::InvalidParams(format!("invalid hex bytes {s:?}: {msg}"));
+
+    if s.is_empty() {
+        return Ok(Bytes::default());
+    }
+    let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).ok_or_else(|| err("missing 0x prefix"))?;
+    if hex.len() % 2 != 0 {
+        return Err(err("odd number of digits"));
+    }
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|p| u8::from_str_radix(p, 16).ok())
+                .ok_or_else(|| err("not hex"))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Bytes)
+}
+
+impl std::ops::Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(v: Vec<u8>) -> Self {
+        Bytes(v)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(v: &[u8]) -> Self {
+        Bytes(v.to_vec())
+    }
+}
+
+impl std::str::FromStr for Bytes {
+    type Err = OpenoceanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex_bytes(s)
+    }
+}
+
+impl TryFrom<&str> for Bytes {
+    type Error = OpenoceanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        parse_hex_bytes(s)
+    }
+}
+
+impl TryFrom<String> for Bytes {
+    type Error = OpenoceanError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        parse_hex_bytes(&s)
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bytes({})", self.to_hex())
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ser.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = std::borrow::Cow::<str>::deserialize(de)?;
+        parse_hex_bytes(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+
+/// A point in time, in unix milliseconds.
+///
+/// Deserializes leniently from the API's formats: unix seconds or
+/// milliseconds (as numbers or strings) and ISO 8601 dates such as
+/// `"2024-05-01T12:00:00.000Z"` or `"2024-05-01 12:00:00"` (read as UTC).
+/// Serializes as unix milliseconds. With the `chrono` feature it converts to
+/// and from `chrono::DateTime<Utc>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub fn from_unix_millis(millis: i64) -> Self {
+        Timestamp(millis)
+    }
+
+    pub fn from_unix_secs(secs: i64) -> Self {
+        Timestamp(secs.saturating_mul(1000))
+    }
+
+    pub fn now() -> Self {
+        Timestamp::from(SystemTime::now())
+    }
+
+    /// `duration` from now, e.g. a swap deadline.
+    pub fn in_future(duration: Duration) -> Self {
+        Timestamp::from(SystemTime::now() + duration)
+    }
+
+    pub fn unix_millis(self) -> i64 {
+        self.0
+    }
+
+    pub fn unix_secs(self) -> i64 {
+        self.0.div_euclid(1000)
+    }
+
+    pub fn is_expired(self) -> bool {
+        self <= Timestamp::now()
+    }
+
+    /// Time left until this timestamp, `None` once it has passed.
+    pub fn expires_in(self) -> Option<Duration> {
+        let left = self.0.saturating_sub(Timestamp::now().0);
+        (left > 0).then(|| Duration::from_millis(left as u64))
+    }
+
+    pub fn to_system_time(self) -> SystemTime {
+        match self.0 >= 0 {
+            true => UNIX_EPOCH + Duration::from_millis(self.0 as u64),
+            false => UNIX_EPOCH - Duration::from_millis(self.0.unsigned_abs()),
+        }
+    }
+
+    /// `None` outside the range `chrono` can represent.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp_millis(self.0)
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(t: SystemTime) -> Self {
+        match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => Timestamp(d.as_millis() as i64),
+            Err(e) => Timestamp(-(e.duration().as_millis() as i64)),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(t: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(t.timestamp_millis())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = OpenoceanError;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        t.to_datetime()
+            .ok_or_else(|| OpenoceanError::InvalidParams(format!("timestamp {} is out of range", t.0)))
+    }
+}
+
+/// Below this, a unix time is taken as seconds (1e11 s is in the year 5138,
+/// 1e11 ms in 1973).
+const SECONDS_CUTOFF: i64 = 100_000_000_000;
+
+fn from_unix_number(v: i64) -> Timestamp {
+    match v.unsigned_abs() < SECONDS_CUTOFF as u64 {
+        true => Timestamp::from_unix_secs(v),
+        false => Timestamp::from_unix_millis(v),
+    }
+}
+
+// days since 1970-01-01 for a proleptic Gregorian date, `None` on overflow
+fn days_from_civil(y: i64, m: i64, d: i64) -> Option<i64> {
+    let y = if m <= 2 { y.checked_sub(1)? } else { y };
+    let era = if y >= 0 { y } else { y.checked_sub(399)? } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era.checked_mul(146097)?.checked_add(doe - 719468)
+}
+
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(m <= 2), m, d)
+}
+
+/// `YYYY-MM-DD[(T| )HH:MM[:SS[.fff]]][Z|±HH:MM|±HHMM]`, `/` also accepted
+/// as the date separator.
+fn parse_datetime(s: &str) -> Option<Timestamp> {
+    fn num(s: &str) -> Option<i64> {
+        (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())).then(|| s.parse().ok())?
+    }
+
+    let (date, rest) = s.split_at(s.find(['T', 't', ' ']).unwrap_or(s.len()));
+    let mut parts = date.split(['-', '/']);
+    let (y, m, d) = (num(parts.next()?)?, num(parts.next()?)?, num(parts.next()?)?);
+    if parts.next().is_some() || !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+
+    let rest = rest.get(1..).unwrap_or("").trim();
+    let (time, offset) = match rest.find(['Z', 'z', '+', '-']) {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+
+    let mut millis = 0;
+    if !time.is_empty() {
+        let (hms, frac) = time.split_once('.').unwrap_or((time, ""));
+        let mut hms = hms.split(':');
+        let h = num(hms.next()?)?;
+        let min = num(hms.next()?)?;
+        let sec = hms.next().map(num).unwrap_or(Some(0))?;
+        if hms.next().is_some() || h > 23 || min > 59 || sec > 60 {
+            return None;
+        }
+        let frac_ms = match frac {
+            "" => 0,
+            f => num(&format!("{:0<3}", f.get(..f.len().min(3))?))?,
+        };
+        millis = ((h * 60 + min) * 60 + sec) * 1000 + frac_ms;
+    }
+
+    let offset_ms = match offset {
+        "" | "Z" | "z" => 0,
+        o => {
+            let sign = if o.starts_with('-') { -1 } else { 1 };
+            let digits = o.get(1..)?.replace(':', "");
+            if digits.len() != 4 {
+                return None;
+            }
+            sign * (num(digits.get(..2)?)? * 60 + num(digits.get(2..)?)?) * 60_000
+        }
+    };
+
+    let millis = days_from_civil(y, m, d)?.checked_mul(86_400_000)?.checked_add(millis)?.checked_sub(offset_ms)?;
+    Some(Timestamp(millis))
+}
+
+fn parse_timestamp(s: &str) -> Result<Timestamp, OpenoceanError> {
+    let s = s.trim();
+    if let Ok(v) = s.parse::<i64>() {
+        return Ok(from_unix_number(v));
+    }
+    parse_datetime(s).ok_or_else(|| OpenoceanError::InvalidParams(format!("invalid timestamp {s:?}")))
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = OpenoceanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_timestamp(s)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    /// RFC 3339 in UTC, with milliseconds.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.0.div_euclid(86_400_000);
+        let ms = self.0.rem_euclid(86_400_000);
+        let (y, m, d) = civil_from_days(days);
+        write!(
+            f,
+            "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}.{:03}Z",
+            ms / 3_600_000,
+            ms / 60_000 % 60,
+            ms / 1000 % 60,
+            ms % 1000,
+        )
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ser.serialize_i64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+        impl<'de> serde::de::Visitor<'de> for V {
+            type Value = Timestamp;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("unix time or date string")
+            }
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> { Ok(from_unix_number(v)) }
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> { Ok(from_unix_number(v.min(i64::MAX as u64) as i64)) }
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                match v.abs() < SECONDS_CUTOFF as f64 {
+                    true => Ok(Timestamp((v * 1000.0) as i64)),
+                    false => Ok(Timestamp(v as i64)),
+                }
+            }
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where E: serde::de::Error {
+                parse_timestamp(s).map_err(E::custom)
+            }
+        }
+        de.deserialize_any(V)
+    }
+}
+
+/// `#[serde(with = "unix_seconds")]` for fields the API expects in unix
+/// seconds, such as swap deadlines.
+pub(crate) mod unix_seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Timestamp;
+
+    pub fn serialize<S: Serializer>(t: &Timestamp, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_i64(t.unix_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Timestamp, D::Error> {
+        Timestamp::deserialize(de)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u256_lenient_deserialize() {
+        let parse = |json: &str| serde_json::from_str::<U256>(json).map(|v| v.to_string());
+
+        assert_eq!(parse(r#""5000000000000000000""#).unwrap(), "5000000000000000000");
+        assert_eq!(parse("5000000000000000000").unwrap(), "5000000000000000000");
+        assert_eq!(parse("1.0106346115864016e21").unwrap(), "1010634611586401600000");
+        assert_eq!(parse(r#""1.0106346115864016e+21""#).unwrap(), "1010634611586401600000");
+        assert_eq!(parse(r#""0xde0b6b3a7640000""#).unwrap(), "1000000000000000000");
+        assert_eq!(parse(r#""120.000""#).unwrap(), "120");
+        assert_eq!(
+            parse(r#""115792089237316195423570985008687907853269984665640564039457584007913129639935""#).unwrap(),
+            U256::MAX.to_string()
+        );
+
+        assert!(parse(r#""1.5""#).is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse(r#""1e80""#).is_err());
+        assert_eq!(serde_json::to_string(&U256::from(42u64)).unwrap(), r#""42""#);
+    }
+
+    #[test]
+    fn test_token_amount_units() {
+        let amount = TokenAmount::from_units("1.5", 18).unwrap();
+        assert_eq!(amount.raw.to_string(), "1500000000000000000");
+        assert_eq!(amount.to_string(), "1.5");
+
+        assert_eq!(TokenAmount::new(1u64, 6).to_units(), "0.000001");
+        assert_eq!(TokenAmount::new(120_000_000u64, 6).to_units(), "120");
+        assert_eq!(TokenAmount::from_units("0.10", 1).unwrap().raw, U256::from(1u64));
+
+        assert!(TokenAmount::from_units("0.0000001", 6).is_err());
+        assert!(TokenAmount::from_units("1,5", 6).is_err());
+    }
+
+    #[test]
+    fn test_percent_formats() {
+        assert_eq!("0.01%".parse::<Percent>().unwrap(), Percent(0.01));
+        assert_eq!(serde_json::from_str::<Percent>(r#""-0.5 %""#).unwrap(), Percent(-0.5));
+        assert_eq!(serde_json::from_str::<Percent>("3").unwrap(), Percent(3.0));
+        assert!("abc%".parse::<Percent>().is_err());
+
+        assert_eq!(Percent(0.5).to_bps(), BasisPoints(50));
+        assert_eq!(BasisPoints(5).to_percent(), Percent(0.05));
+
+        assert_eq!(serde_json::to_string(&Percent(1.0)).unwrap(), "1");
+        assert_eq!(serde_urlencoded::to_string([("slippage", Percent(0.05))]).unwrap(), "slippage=0.05");
+    }
+
+    #[test]
+    fn test_slippage_range() {
+        assert_eq!(Slippage::try_from("0.5").unwrap().percent(), Percent(0.5));
+        assert_eq!(Slippage::try_from(BasisPoints(5)).unwrap().percent(), Slippage::MIN);
+        assert!(Slippage::try_from(0.01).is_err());
+        assert!(Slippage::try_from(51).is_err());
+        assert_eq!(Slippage::default().percent(), Percent(1.0));
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let json = r#""0x90411a32000000000000000000000000ab""#;
+        let data: Bytes = serde_json::from_str(json).unwrap();
+        assert_eq!(data.len(), 17);
+        assert_eq!(data.selector(), Some([0x90, 0x41, 0x1a, 0x32]));
+        assert_eq!(serde_json::to_string(&data).unwrap(), json);
+
+        assert!(serde_json::from_str::<Bytes>(r#""""#).unwrap().is_empty());
+        assert_eq!(Bytes::default().to_string(), "0x");
+        assert!("0x123".parse::<Bytes>().is_err());
+        assert!("0xzz".parse::<Bytes>().is_err());
+        assert!("1234".parse::<Bytes>().is_err());
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let parse = |json: &str| serde_json::from_str::<Timestamp>(json).unwrap().unix_millis();
+
+        assert_eq!(parse("1714564800"), 1714564800000);
+        assert_eq!(parse("1714564800123"), 1714564800123);
+        assert_eq!(parse(r#""1714564800""#), 1714564800000);
+        assert_eq!(parse(r#""2024-05-01T12:00:00.123Z""#), 1714564800123);
+        assert_eq!(parse(r#""2024-05-01 12:00:00""#), 1714564800000);
+        assert_eq!(parse(r#""2024-05-01T20:00:00+08:00""#), 1714564800000);
+        assert_eq!(parse(r#""2024/05/01""#), 1714521600000);
+        assert!(serde_json::from_str::<Timestamp>(r#""yesterday""#).is_err());
+
+        // malformed or out-of-range values are errors, not panics
+        for bad in [r#""2024-05-01T12:00:00.ééé""#, r#""2024-05-01T12:00:00+1é1""#, r#""9999999999999-01-01""#] {
+            assert!(serde_json::from_str::<Timestamp>(bad).is_err(), "{bad}");
+        }
+        assert_eq!(parse(r#""-9223372036854775808""#), i64::MIN);
+        assert_eq!(parse("-9223372036854775808"), i64::MIN);
+
+        let t = Timestamp::from_unix_millis(1714564800123);
+        assert_eq!(t.to_string(), "2024-05-01T12:00:00.123Z");
+        assert_eq!(t.to_string().parse::<Timestamp>().unwrap(), t);
+    }
+
+    #[test]
+    fn test_timestamp_expiry() {
+        assert!(Timestamp::from_unix_secs(1).is_expired());
+        assert_eq!(Timestamp::from_unix_secs(1).expires_in(), None);
+
+        let deadline = Timestamp::in_future(Duration::from_secs(600));
+        assert!(!deadline.is_expired());
+        assert!(deadline.expires_in().unwrap() > Duration::from_secs(590));
+
+        assert_eq!(Timestamp::from_unix_millis(i64::MIN).expires_in(), None);
+        assert!(Timestamp::from_unix_millis(i64::MAX).expires_in().is_some());
+    }
+
+    #[test]
+    fn test_unix_seconds_field() {
+        #[derive(Serialize, Deserialize)]
+        struct Params {
+            #[serde(with = "unix_seconds")]
+            deadline: Timestamp,
+        }
+
+        let params = Params { deadline: Timestamp::from_unix_millis(1714564800999) };
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"deadline":1714564800}"#);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_chrono() {
+        let t = Timestamp::from_unix_millis(1714564800123);
+        let dt: chrono::DateTime<chrono::Utc> = t.try_into().unwrap();
+        assert_eq!(dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true), t.to_string());
+        assert_eq!(Timestamp::from(dt), t);
+
+        assert_eq!(Timestamp::from_unix_millis(i64::MAX).to_datetime(), None);
+        assert!(chrono::DateTime::<chrono::Utc>::try_from(Timestamp::from_unix_millis(i64::MIN)).is_err());
+    }
+}